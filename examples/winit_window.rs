@@ -3,20 +3,25 @@
 use std::{sync::Arc, time::Instant};
 
 use cgmath::{Basis3, Deg, Matrix4, One, Vector2, Vector3};
-use sifu_render::shader::Shader;
-use sifu_render::UniformExt;
+use sifu_render::mesh::TriangleList;
+use sifu_render::shader::{Pipeline, Shader};
+use sifu_render::{UniformArena, UniformArrayBuffer, UniformExt};
 use sifu_render::{
     GpuBuffer,
+    render_graph::{RenderGraph, TexturePool, TextureSize},
     sample::{
-        camera_uniform::{Camera, FooUniformsDerive},
+        camera_uniform::{Camera, FooUniformsDerive, Projection},
         sample_shader,
         sample_vertex::{SampleInstance, SampleUniform, SampleVertex},
     },
-    texture::{Color, DepthStencilPixel, ImageTexture, PixelFormat, RenderTexture},
+    texture::{
+        AttachmentOps, Color, DepthStencilClear, DepthStencilPixel, ImageTexture, PixelFormat,
+        RenderTarget, RenderTexture,
+    },
     uniform_binding::UniformBinding,
 };
 use wgpu::{
-    Adapter, Backends, BindGroup, BindGroupDescriptor, BindGroupLayoutDescriptor, BlendState, ColorTargetState, ColorWrites, CommandEncoder, CommandEncoderDescriptor, CompareFunction, DepthBiasState, DepthStencilState, Device, DeviceDescriptor, Features, FragmentState, FrontFace, Instance, InstanceDescriptor, MultisampleState, Operations, PipelineCompilationOptions, PipelineLayoutDescriptor, PolygonMode, PrimitiveState, PrimitiveTopology, Queue, RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor, RequestAdapterOptions, SamplerDescriptor, StencilFaceState, StencilState, Surface, TextureFormat, TextureView
+    Adapter, Backends, BindGroup, BindGroupDescriptor, BindGroupLayoutDescriptor, BlendState, ColorTargetState, ColorWrites, CommandEncoder, CommandEncoderDescriptor, CompareFunction, DepthBiasState, DepthStencilState, Device, DeviceDescriptor, DynamicOffset, Features, FragmentState, FrontFace, Instance, InstanceDescriptor, MultisampleState, Operations, PipelineCompilationOptions, PipelineLayoutDescriptor, PolygonMode, PrimitiveState, PrimitiveTopology, Queue, RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor, RequestAdapterOptions, Sampler, SamplerDescriptor, StencilFaceState, StencilState, Surface, TextureFormat, TextureView
 };
 use winit::{
     application::ApplicationHandler,
@@ -26,6 +31,8 @@ use winit::{
     window::{Window, WindowAttributes},
 };
 
+const SAMPLES: u32 = 4;
+
 #[derive(Default)]
 struct App {
     ctx: Option<RenderContext>,
@@ -95,8 +102,10 @@ struct RenderContext {
     window: Arc<Window>,
     adapter: Adapter,
     surface: Surface<'static>,
-    depth_texture: RenderTexture<DepthStencilPixel, true>,
-    multisample_texture: RenderTexture<(), true>,
+    texture_pool: TexturePool,
+    uniform_arena: UniformArena,
+    sample_uniforms: UniformArrayBuffer<SampleUniform>,
+    sampler: Sampler,
     mesh_texture: ImageTexture,
     device: Device,
     queue: Queue,
@@ -134,27 +143,47 @@ impl RenderContext {
 
         Self::configure_surface(&surface, &device, &adapter, &window);
 
-        let surface_format = Self::get_format(&surface, &adapter);
 
-        let depth_texture = RenderTexture::new(&device, window_size);
-        let multisample_texture = RenderTexture::new_format(&device, window_size, surface_format);
-
-        let mesh_texture =
-            ImageTexture::new_procedural(&device, &queue, Vector2::new(16, 16), |pos| {
+        let mesh_texture = ImageTexture::new_procedural(
+            &device,
+            &queue,
+            Vector2::new(16, 16),
+            |pos| {
                 if (pos.x + pos.y) % 2 == 0 {
                     Color::BLACK
                 } else {
                     Color::WHITE
                 }
-            });
+            },
+            Some("checkerboard"),
+        );
+
+        let sample_uniforms = GpuBuffer::uniform_array(
+            &device,
+            [
+                SampleUniform {
+                    opacity: 0.9,
+                    color_a: Vector3::new(1.0, 0.0, 0.5),
+                    color_b: Vector3::new(0.2, 0.9, 0.4),
+                },
+                SampleUniform {
+                    opacity: 0.0,
+                    color_a: Vector3::new(0.4, 0.1, 1.0),
+                    color_b: Vector3::new(0.1, 0.2, 0.6),
+                },
+            ],
+            None,
+        );
 
         Self {
             window,
             instance,
             adapter,
             surface,
-            depth_texture,
-            multisample_texture,
+            texture_pool: TexturePool::new(),
+            uniform_arena: UniformArena::new(&device, 4096),
+            sample_uniforms,
+            sampler: device.create_sampler(&SamplerDescriptor::default()),
             mesh_texture,
             device,
             queue,
@@ -184,8 +213,6 @@ impl RenderContext {
                 .get_default_config(&self.adapter, size.x, size.y)
                 .unwrap(),
         );
-        self.depth_texture.resize(&self.device, size);
-        self.multisample_texture.resize(&self.device, size);
     }
 
     pub fn render(&mut self) {
@@ -194,46 +221,31 @@ impl RenderContext {
 
         // Create uniforms and bind groups
 
-        let sample_uniform = SampleUniform {
-            opacity: 0.9,
-            color_a: Vector3::new(1.0, 0.0, 0.5),
-            color_b: Vector3::new(0.2, 0.9, 0.4),
-        }
-        .buffer(&self.device);
-
-        let sample_uniform2 = GpuBuffer::uniform(
+        let camera = self.uniform_arena.push(
             &self.device,
-            SampleUniform {
-                opacity: 0.0,
-                color_a: Vector3::new(0.4, 0.1, 1.0),
-                color_b: Vector3::new(0.1, 0.2, 0.6),
-            },
+            &self.queue,
+            Camera {
+                position: Vector3::new(0.0, 0.0, 2.0),
+                rotation: Basis3::one(),
+                fovy: Deg(90.0).into(),
+                screen_size: output.size().cast().unwrap(),
+                projection: Projection::default(),
+            }
+            .uniform(),
         );
 
-        let camera = Camera {
-            position: Vector3::new(0.0, 0.0, 2.0),
-            rotation: Basis3::one(),
-            fovy: Deg(90.0).into(),
-            screen_size: output.size().cast().unwrap(),
-        }
-        .uniform()
-        .buffer(&self.device);
-
-        let sampler = self.device.create_sampler(&SamplerDescriptor::default());
-
         let foo_uniforms = FooUniformsDerive {
-            sample: &sample_uniform,
+            sample: &self.sample_uniforms,
             camera: &camera,
             texture: &self.mesh_texture,
-            tex_sampler: &sampler,
+            tex_sampler: &self.sampler,
         };
 
-        let foo_uniforms2 = FooUniformsDerive {
-            sample: &sample_uniform2,
-            camera: &camera,
-            texture: &self.mesh_texture,
-            tex_sampler: &sampler,
-        };
+        // Both sample uniforms share the bind group, they are selected with dynamic offsets
+        let sample_offsets = [
+            foo_uniforms.dynamic_offsets(&[0]),
+            foo_uniforms.dynamic_offsets(&[1]),
+        ];
 
         let bind_group_layout = self
             .device
@@ -248,12 +260,6 @@ impl RenderContext {
             entries: &foo_uniforms.binding_entries(),
         });
 
-        let bind_group2 = self.device.create_bind_group(&BindGroupDescriptor {
-            label: Some("Foo BindGroup2"),
-            layout: &bind_group_layout,
-            entries: &foo_uniforms2.binding_entries(),
-        });
-
         // Create pipeline layout
 
         let pipeline_layout = self
@@ -266,9 +272,9 @@ impl RenderContext {
 
         let shader = sample_shader(&self.device);
 
-        let render_pipeline = self
-            .device
-            .create_render_pipeline(&RenderPipelineDescriptor {
+        let render_pipeline = Pipeline::<TriangleList>::new(
+            &self.device,
+            RenderPipelineDescriptor {
                 label: Some("Foo RenderPipeline"),
                 layout: Some(&pipeline_layout),
                 vertex: shader.vertex_state(),
@@ -304,12 +310,12 @@ impl RenderContext {
                     bias: DepthBiasState::default(),
                 }),
                 multisample: MultisampleState {
-                    count: 4,
+                    count: RenderTexture::<(), SAMPLES>::sample_count(),
                     mask: !0,
                     alpha_to_coverage_enabled: false,
                 },
                 fragment: Some(FragmentState {
-                    module: &shader.module(),
+                    module: shader.module(),
                     entry_point: Some(Shader::<(), ()>::ENTRY_POINT_FRAGMENT),
                     targets: &[Some(ColorTargetState {
                         format: output.format(),
@@ -320,7 +326,8 @@ impl RenderContext {
                 }),
                 multiview: None,
                 cache: None,
-            });
+            },
+        );
 
         let mut encoder = self
             .device
@@ -328,33 +335,56 @@ impl RenderContext {
                 label: Some("RenderContext::render"),
             });
 
-        let target = RenderTarget::MultisampleResolve {
-            multisample_color: &self.multisample_texture,
-            resolve_color: &output,
-            depth: Some(&self.depth_texture),
-        };
+        // Depth and multisample textures are transient, the graph resizes them with the output
+        let mut graph = RenderGraph::new(output.size());
 
-        Self::render_pass(
-            &self.device,
-            &mut encoder,
-            target,
-            &render_pipeline,
-            &bind_group,
-            &bind_group2,
-            self.start_time,
+        let surface = graph.import("surface");
+        graph.mark_output(surface);
+
+        let depth = graph.create_texture::<DepthStencilPixel, SAMPLES>("depth", TextureSize::Relative(1.0));
+        let multisample = graph.create_texture_format::<SAMPLES>(
+            "multisample color",
+            TextureSize::Relative(1.0),
+            output.format(),
         );
 
+        graph
+            .add_pass("Foo RenderPass")
+            .writes(multisample)
+            .writes(depth)
+            .writes(surface)
+            .execute(|encoder, resources| {
+                let target = RenderTarget::resolve(resources.texture(multisample), &output)
+                    .with_depth(resources.texture(depth))
+                    .with_depth_ops(AttachmentOps::clear(DepthStencilClear::new(1.0, 0)).discard());
+
+                Self::render_pass(
+                    &self.device,
+                    encoder,
+                    target,
+                    &render_pipeline,
+                    &bind_group,
+                    &sample_offsets,
+                    self.start_time,
+                );
+            });
+
+        graph
+            .execute(&self.device, &mut self.texture_pool, &mut encoder)
+            .unwrap();
+
         self.queue.submit([encoder.finish()]);
+        self.uniform_arena.end_frame(&self.queue);
         output.present();
     }
 
     fn render_pass(
         device: &Device,
         encoder: &mut CommandEncoder,
-        target: RenderTarget<()>,
-        pipeline: &RenderPipeline,
+        target: RenderTarget<RenderTexture<(), SAMPLES>, DepthStencilPixel, SAMPLES>,
+        pipeline: &Pipeline<TriangleList>,
         bind_group: &BindGroup,
-        bind_group2: &BindGroup,
+        sample_offsets: &[Vec<DynamicOffset>; 2],
         start_time: Instant,
     ) {
         let mesh = SampleVertex::sample_mesh(device);
@@ -367,6 +397,7 @@ impl RenderContext {
             [SampleInstance {
                 mat: Matrix4::from_angle_y(Deg(secs * 180.0)),
             }],
+            None,
         );
 
         let instance2 = GpuBuffer::instances(
@@ -375,6 +406,7 @@ impl RenderContext {
                 mat: Matrix4::from_translation(Vector3::new(0.0, 0.0, -5.0))
                     * Matrix4::from_angle_y(Deg(secs * -90.0)),
             }],
+            None,
         );
 
         let instance3 = GpuBuffer::instances(
@@ -384,112 +416,25 @@ impl RenderContext {
                     * Matrix4::from_scale(20.0)
                     * Matrix4::from_angle_y(Deg(secs * 10.0)),
             }],
+            None,
         );
 
-        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
-            label: Some("Foo RenderPass"),
-            color_attachments: &[Some(RenderPassColorAttachment {
-                view: target.view(),
-                resolve_target: target.resolve_target(),
-                ops: Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
-            depth_stencil_attachment: target.depth().map(|view| RenderPassDepthStencilAttachment {
-                view,
-                depth_ops: Some(Operations {
-                    load: wgpu::LoadOp::Clear(1.0),
-                    store: wgpu::StoreOp::Discard,
-                }),
-                stencil_ops: Some(Operations {
-                    load: wgpu::LoadOp::Clear(0),
-                    store: wgpu::StoreOp::Discard,
-                }),
-            }),
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
-
-        pass.set_pipeline(pipeline);
-
+        let mut pass = target.begin_pass(encoder, Some("Foo RenderPass"));
 
 
         // draw big mesh in the far back
         // pass.set_stencil_reference(2);
-        pass.set_bind_group(0, bind_group, &[]);
-        mesh.draw(&instance3, &mut pass);
+        pass.set_bind_group(0, bind_group, &sample_offsets[0]);
+        mesh.draw_with(pipeline, &instance3, &mut pass);
 
         // draw blue mesh in the back
         pass.set_stencil_reference(2);
-        pass.set_bind_group(0, bind_group2, &[]);
-        mesh.draw(&instance2, &mut pass);
+        pass.set_bind_group(0, bind_group, &sample_offsets[1]);
+        mesh.draw_with(pipeline, &instance2, &mut pass);
 
         // draw rotating front mesh
         pass.set_stencil_reference(1);
-        pass.set_bind_group(0, bind_group, &[]);
-        mesh.draw(&instance, &mut pass);
-    }
-}
-
-enum RenderTarget<'a, P> {
-    NonMultisample {
-        color: &'a RenderTexture<P, false>,
-        depth: Option<&'a RenderTexture<DepthStencilPixel, false>>,
-    },
-
-    Multisample {
-        color: &'a RenderTexture<P, true>,
-        depth: Option<&'a RenderTexture<DepthStencilPixel, true>>,
-    },
-
-    MultisampleResolve {
-        multisample_color: &'a RenderTexture<P, true>,
-        resolve_color: &'a RenderTexture<P, false>,
-        depth: Option<&'a RenderTexture<DepthStencilPixel, true>>,
-    },
-}
-
-impl<'a, P> RenderTarget<'a, P> {
-    fn is_multisampled(&self) -> bool {
-        match self {
-            RenderTarget::NonMultisample { .. } => false,
-            RenderTarget::Multisample { .. } => true,
-            RenderTarget::MultisampleResolve { .. } => false,
-        }
-    }
-
-    fn view(&self) -> &TextureView {
-        match self {
-            RenderTarget::NonMultisample { color, .. } => color.view(),
-            RenderTarget::Multisample { color, .. } => color.view(),
-            RenderTarget::MultisampleResolve {
-                multisample_color, ..
-            } => multisample_color.view(),
-        }
-    }
-
-    fn resolve_target(&self) -> Option<&TextureView> {
-        match self {
-            RenderTarget::NonMultisample { .. } => None,
-            RenderTarget::Multisample { .. } => None,
-            RenderTarget::MultisampleResolve { resolve_color, .. } => Some(resolve_color.view()),
-        }
-    }
-
-    fn depth(&self) -> Option<&TextureView> {
-        match self {
-            RenderTarget::NonMultisample {
-                depth: Some(depth), ..
-            } => Some(depth.view()),
-            RenderTarget::Multisample {
-                depth: Some(depth), ..
-            } => Some(depth.view()),
-            RenderTarget::MultisampleResolve {
-                depth: Some(depth), ..
-            } => Some(depth.view()),
-            _ => None,
-        }
+        pass.set_bind_group(0, bind_group, &sample_offsets[0]);
+        mesh.draw_with(pipeline, &instance, &mut pass);
     }
 }
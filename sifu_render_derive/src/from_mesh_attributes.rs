@@ -0,0 +1,74 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DeriveInput;
+
+/// The helper attributes, in the order of the fields of `MeshAttributes`.
+pub(crate) const MESH_ATTRIBUTES: &[&str] = &["position", "normal", "uv", "tangent", "joints", "weights"];
+
+/// Reads the mesh attribute `name` from `attributes`.
+fn attribute_value(name: &str) -> TokenStream {
+    match name {
+        "position" => quote! { attributes.position },
+        "normal" => quote! { attributes.normal_or_zero() },
+        "uv" => quote! { attributes.uv_or_zero() },
+        "tangent" => quote! { attributes.tangent_or_zero() },
+        "joints" => quote! { attributes.joints_or_zero() },
+        "weights" => quote! { attributes.weights_or_zero() },
+        _ => unreachable!("unknown mesh attribute {name}"),
+    }
+}
+
+/// The mesh attribute `field` is marked with, if any.
+pub(crate) fn marked_attribute(field: &syn::Field) -> syn::Result<Option<&'static str>> {
+    let mut marked = MESH_ATTRIBUTES.iter().filter(|name| {
+        field.attrs.iter().any(|attr| attr.path().is_ident(name))
+    });
+
+    match (marked.next(), marked.next()) {
+        (None, _) => Ok(None),
+        (Some(name), None) => Ok(Some(name)),
+        (Some(_), Some(_)) => Err(syn::Error::new_spanned(&field.ident, "a field can only be marked with one mesh attribute")),
+    }
+}
+
+pub fn impl_from_mesh_attributes_quote(input: DeriveInput) -> TokenStream {
+    let ident = &input.ident;
+
+    let data_struct = match input.data {
+        syn::Data::Struct(data_struct) => data_struct,
+        _ => return syn::Error::new(ident.span(), "FromMeshAttributes only works on structs").into_compile_error()
+    };
+
+    if data_struct.fields.iter().any(|field| field.ident.is_none()) {
+        return syn::Error::new(ident.span(), "FromMeshAttributes does only work for named fields").into_compile_error()
+    }
+
+    let mut fields = Vec::new();
+
+    for field in &data_struct.fields {
+        let field_ident = field.ident.as_ref().unwrap();
+
+        let value = match marked_attribute(field) {
+            Ok(None) => quote! { ::core::default::Default::default() },
+            Ok(Some(name)) => {
+                let value = attribute_value(name);
+                quote! { ::core::convert::Into::into(#value) }
+            }
+            Err(err) => return err.into_compile_error(),
+        };
+
+        fields.push(quote! { #field_ident: #value });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics sifu_render::mesh::FromMeshAttributes for #ident #ty_generics #where_clause {
+            fn from_mesh_attributes(attributes: &sifu_render::mesh::MeshAttributes) -> Self {
+                Self {
+                    #(#fields),*
+                }
+            }
+        }
+    }
+}
@@ -7,6 +7,8 @@ mod raw_rust_type;
 mod wgsl_type_str;
 mod get_ident;
 mod uniform_binding;
+mod from_mesh_attributes;
+mod vertex_attributes;
 
 
 use proc_macro::TokenStream;
@@ -14,6 +16,8 @@ use quotes::{mesh_instance::instance_quote, uniform::uniform_quote, vertex::vert
 use syn::{DeriveInput, parse_macro_input};
 use parse::ParsedStruct;
 use uniform_binding::impl_uniform_binding_quote;
+use from_mesh_attributes::impl_from_mesh_attributes_quote;
+use vertex_attributes::impl_vertex_attributes_quote;
 
 #[proc_macro_derive(Vertex, attributes(raw))]
 pub fn derive_vertex(input: TokenStream) -> TokenStream {
@@ -33,7 +37,7 @@ fn parse_vertex(derive_input: DeriveInput) -> syn::Result<TokenStream> {
     Ok(result.into())
 }
 
-#[proc_macro_derive(MeshInstance, attributes(vertex, raw))]
+#[proc_macro_derive(MeshInstance, attributes(vertex, raw, position))]
 pub fn derive_instance(input: TokenStream) -> TokenStream {
     let derive_input: DeriveInput = parse_macro_input!(input);
 
@@ -70,7 +74,7 @@ fn parse_uniform(derive_input: DeriveInput) -> syn::Result<TokenStream> {
     Ok(result.into())
 }
 
-#[proc_macro_derive(UniformBinding, attributes())]
+#[proc_macro_derive(UniformBinding, attributes(dynamic))]
 pub fn derive_uniform_binding(input: TokenStream) -> TokenStream {
     let derive_input: DeriveInput = parse_macro_input!(input);
 
@@ -80,4 +84,18 @@ pub fn derive_uniform_binding(input: TokenStream) -> TokenStream {
     // }
 
     impl_uniform_binding_quote(derive_input).into()
-}
\ No newline at end of file
+}
+
+#[proc_macro_derive(FromMeshAttributes, attributes(position, normal, uv, tangent, joints, weights))]
+pub fn derive_from_mesh_attributes(input: TokenStream) -> TokenStream {
+    let derive_input: DeriveInput = parse_macro_input!(input);
+
+    impl_from_mesh_attributes_quote(derive_input).into()
+}
+
+#[proc_macro_derive(VertexAttributes, attributes(position, normal, uv, tangent, joints, weights))]
+pub fn derive_vertex_attributes(input: TokenStream) -> TokenStream {
+    let derive_input: DeriveInput = parse_macro_input!(input);
+
+    impl_vertex_attributes_quote(derive_input).into()
+}
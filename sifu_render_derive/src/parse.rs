@@ -76,6 +76,8 @@ pub struct RawField<T> {
     pub ident: syn::Ident,
     pub source_ty: syn::Type,
     pub raw_ty: T,
+    /// Marked with `#[position]`, only used by `MeshInstance`
+    pub position: bool,
 }
 
 impl<T> RawField<WgslType<T>>
@@ -110,10 +112,16 @@ where
             Err(err) => return Err(err),
         };
 
+        let position = field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("position"));
+
         Ok(Self {
             ident,
             source_ty: field.ty,
             raw_ty,
+            position,
         })
     }
 }
@@ -3,7 +3,7 @@ use quote::quote;
 use syn::{Ident, Type};
 
 use crate::{
-    parse::{ParsedStruct, RawField}, repeated_raw_field::RepeatedRawField, raw_value::{rust_scalar::RustScalar, wgsl_type::WgslType}
+    parse::{ParsedStruct, RawField}, repeated_raw_field::RepeatedRawField, raw_value::{dim::Dim, rust_scalar::RustScalar, wgsl_type::WgslType}
 };
 
 use super::shared::{raw_struct_quote, shader_struct_format_quote, vertex_attributes_quote};
@@ -44,6 +44,8 @@ fn impl_instance_quote(
 
     let vertex_attributes = vertex_attributes_quote(repeated_fields, Some(&initial_offset_ident));
 
+    let position = position_quote(fields)?;
+
     Ok(quote! {
         impl sifu_render::mesh::MeshInstance for #ident {
             type Vertex = #vertex_ty;
@@ -63,6 +65,40 @@ fn impl_instance_quote(
 
                 &SHADER
             }
+
+            #position
+        }
+    })
+}
+
+/// The `position` method for the field marked with `#[position]`, a 3d vector or a 4x4 matrix whose translation is used.
+fn position_quote(fields: &[RawField<WgslType<RustScalar>>]) -> syn::Result<TokenStream> {
+    let mut marked = fields.iter().filter(|field| field.position);
+
+    let Some(field) = marked.next() else {
+        return Ok(TokenStream::new());
+    };
+
+    if let Some(other) = marked.next() {
+        return Err(syn::Error::new(other.ident.span(), "only one field can be marked with #[position]"));
+    }
+
+    let ident = &field.ident;
+
+    let position = match field.raw_ty {
+        WgslType::Vec { dim: Dim::D3, .. } => quote! {
+            ::core::convert::Into::into(::core::clone::Clone::clone(&self.#ident))
+        },
+        WgslType::Mat { dim_x: Dim::D4, dim_y: Dim::D4, .. } => quote! {{
+            let matrix: sifu_render::cgmath::Matrix4<f32> = ::core::convert::Into::into(::core::clone::Clone::clone(&self.#ident));
+            matrix.w.truncate()
+        }},
+        _ => return Err(syn::Error::new(ident.span(), "#[position] needs a field with 3 components or a 4x4 matrix")),
+    };
+
+    Ok(quote! {
+        fn position(&self) -> ::core::option::Option<sifu_render::cgmath::Vector3<f32>> {
+            ::core::option::Option::Some(#position)
         }
     })
 }
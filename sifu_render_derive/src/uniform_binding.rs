@@ -42,6 +42,39 @@ pub fn impl_uniform_binding_quote(input: DeriveInput) -> TokenStream {
         }
     });
 
+    // A dynamic binding without `#[dynamic]` or the other way around would only fail wgpu validation at runtime
+    let dynamic_checks = data_struct.fields.iter().map(|field| {
+        let ty = &field.ty;
+        let is_dynamic = field.attrs.iter().any(|attr| attr.path().is_ident("dynamic"));
+        let message = match is_dynamic {
+            true => format!("field `{}` is marked with #[dynamic], but its binding has no dynamic offset", field.ident.as_ref().unwrap()),
+            false => format!("field `{}` has a dynamic offset and needs the #[dynamic] attribute", field.ident.as_ref().unwrap()),
+        };
+
+        quote! {
+            assert!(
+                <#ty as sifu_render::uniform_binding::AsBindingResource>::HAS_DYNAMIC_OFFSET == #is_dynamic,
+                #message
+            );
+        }
+    });
+
+    // Offsets are collected in binding order, which is the field order
+    let dynamic_fields = data_struct
+        .fields
+        .iter()
+        .filter(|field| field.attrs.iter().any(|attr| attr.path().is_ident("dynamic")))
+        .enumerate()
+        .map(|(idx, field)| (idx, field.ident.as_ref().unwrap(), &field.ty));
+
+    let dynamic_offset_count = dynamic_fields.clone().count();
+
+    let dynamic_offset_fields = dynamic_fields.map(|(idx, ident, ty)| {
+        quote! {
+            <#ty as sifu_render::uniform_binding::DynamicBindingResource>::dynamic_offset(&self.#ident, indices[#idx])
+        }
+    });
+
     let glsl_var_fields = fields.clone().map(|(idx, ident, ty)| {
         let name = ident.to_string();
         quote! {
@@ -56,9 +89,13 @@ pub fn impl_uniform_binding_quote(input: DeriveInput) -> TokenStream {
 
     quote! {
         impl #generics sifu_render::uniform_binding::UniformBinding for #ident #generics {
-            const LAYOUT: &'static [sifu_render::uniform_binding::wgpu::BindGroupLayoutEntry] = &[
-                #(#layout_fields),*
-            ];
+            const LAYOUT: &'static [sifu_render::uniform_binding::wgpu::BindGroupLayoutEntry] = {
+                #(#dynamic_checks)*
+
+                &[
+                    #(#layout_fields),*
+                ]
+            };
 
             fn binding_entries(&self) -> Vec<sifu_render::uniform_binding::wgpu::BindGroupEntry> {
                 vec![
@@ -66,6 +103,20 @@ pub fn impl_uniform_binding_quote(input: DeriveInput) -> TokenStream {
                 ]
             }
 
+            const DYNAMIC_OFFSET_COUNT: usize = #dynamic_offset_count;
+
+            fn dynamic_offsets(&self, indices: &[u32]) -> Vec<u32> {
+                assert_eq!(
+                    indices.len(),
+                    #dynamic_offset_count,
+                    "expected one index per dynamic binding"
+                );
+
+                vec![
+                    #(#dynamic_offset_fields),*
+                ]
+            }
+
             fn glsl_vars(group_id: u32) -> Vec<sifu_render::uniform_binding::GlslUniformVar> {
                 vec![
                     #(#glsl_var_fields),*
@@ -0,0 +1,90 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+use crate::from_mesh_attributes::{MESH_ATTRIBUTES, marked_attribute};
+
+/// The cgmath type of the mesh attribute `name`.
+fn attribute_type(name: &str) -> TokenStream {
+    match name {
+        "position" | "normal" => quote! { sifu_render::cgmath::Vector3<f32> },
+        "uv" => quote! { sifu_render::cgmath::Vector2<f32> },
+        "tangent" | "weights" => quote! { sifu_render::cgmath::Vector4<f32> },
+        "joints" => quote! { sifu_render::cgmath::Vector4<u32> },
+        _ => unreachable!("unknown mesh attribute {name}"),
+    }
+}
+
+pub fn impl_vertex_attributes_quote(input: DeriveInput) -> TokenStream {
+    let ident = &input.ident;
+
+    let data_struct = match input.data {
+        syn::Data::Struct(data_struct) => data_struct,
+        _ => return syn::Error::new(ident.span(), "VertexAttributes only works on structs").into_compile_error()
+    };
+
+    if data_struct.fields.iter().any(|field| field.ident.is_none()) {
+        return syn::Error::new(ident.span(), "VertexAttributes does only work for named fields").into_compile_error()
+    }
+
+    let mut fields = Vec::new();
+
+    for field in &data_struct.fields {
+        match marked_attribute(field) {
+            Ok(Some(name)) => fields.push((name, field.ident.as_ref().unwrap())),
+            Ok(None) => {}
+            Err(err) => return err.into_compile_error(),
+        }
+    }
+
+    let mut methods = Vec::new();
+
+    for name in MESH_ATTRIBUTES {
+        let getter = format_ident!("{name}");
+        let setter = format_ident!("set_{name}");
+        let ty = attribute_type(name);
+
+        let field = fields.iter().find(|(field_name, _)| field_name == name).map(|(_, field)| field);
+
+        let (get, set) = match (*name, field) {
+            ("position", None) => {
+                return syn::Error::new(ident.span(), "VertexAttributes needs a field marked with #[position]").into_compile_error()
+            }
+            ("position", Some(field)) => (
+                quote! { ::core::convert::Into::into(::core::clone::Clone::clone(&self.#field)) },
+                quote! { self.#field = ::core::convert::Into::into(value); },
+            ),
+            (_, Some(field)) => (
+                quote! { ::core::option::Option::Some(::core::convert::Into::into(::core::clone::Clone::clone(&self.#field))) },
+                quote! { self.#field = ::core::convert::Into::into(value); },
+            ),
+            (_, None) => (
+                quote! { ::core::option::Option::None },
+                quote! { let _ = value; },
+            ),
+        };
+
+        let get_ty = match *name {
+            "position" => ty.clone(),
+            _ => quote! { ::core::option::Option<#ty> },
+        };
+
+        methods.push(quote! {
+            fn #getter(&self) -> #get_ty {
+                #get
+            }
+
+            fn #setter(&mut self, value: #ty) {
+                #set
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics sifu_render::mesh::VertexAttributes for #ident #ty_generics #where_clause {
+            #(#methods)*
+        }
+    }
+}
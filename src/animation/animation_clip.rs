@@ -0,0 +1,131 @@
+use cgmath::{Quaternion, Vector3};
+
+use super::{JointTransform, Keyframes};
+
+/// The animated property of a [`Channel`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelValues {
+    Translation(Keyframes<Vector3<f32>>),
+    Rotation(Keyframes<Quaternion<f32>>),
+    Scale(Keyframes<Vector3<f32>>),
+}
+
+/// Animates one property of one joint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Channel {
+    /// An index into [`Skeleton::joints`](super::Skeleton::joints)
+    pub joint: usize,
+    pub values: ChannelValues,
+}
+
+/// A named animation of the joints of a [`Skeleton`](super::Skeleton).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationClip {
+    pub name: Option<String>,
+    pub channels: Vec<Channel>,
+}
+
+impl AnimationClip {
+    pub fn new(name: Option<String>, channels: Vec<Channel>) -> Self {
+        Self { name, channels }
+    }
+
+    /// The time of the last keyframe of all channels.
+    pub fn duration(&self) -> f32 {
+        self.channels
+            .iter()
+            .map(|channel| match &channel.values {
+                ChannelValues::Translation(keyframes) | ChannelValues::Scale(keyframes) => {
+                    keyframes.duration()
+                }
+                ChannelValues::Rotation(keyframes) => keyframes.duration(),
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// Overwrites the animated properties of the joints in `pose` with their value at `time`, the others keep their value.
+    ///
+    /// Channels of joints outside of `pose` are ignored.
+    pub fn sample(&self, time: f32, pose: &mut [JointTransform]) {
+        for channel in &self.channels {
+            let Some(transform) = pose.get_mut(channel.joint) else {
+                continue;
+            };
+
+            match &channel.values {
+                ChannelValues::Translation(keyframes) => {
+                    if let Some(translation) = keyframes.sample(time) {
+                        transform.translation = translation;
+                    }
+                }
+                ChannelValues::Rotation(keyframes) => {
+                    if let Some(rotation) = keyframes.sample(time) {
+                        transform.rotation = rotation;
+                    }
+                }
+                ChannelValues::Scale(keyframes) => {
+                    if let Some(scale) = keyframes.sample(time) {
+                        transform.scale = scale;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Like [`AnimationClip::sample`], but `time` wraps around at the [`duration`](AnimationClip::duration).
+    pub fn sample_looped(&self, time: f32, pose: &mut [JointTransform]) {
+        let duration = self.duration();
+
+        let time = match duration > 0.0 {
+            true => time.rem_euclid(duration),
+            false => 0.0,
+        };
+
+        self.sample(time, pose);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Vector3;
+
+    use super::{AnimationClip, Channel, ChannelValues};
+    use crate::animation::{Interpolation, JointTransform, Keyframes};
+
+    fn clip() -> AnimationClip {
+        AnimationClip::new(
+            None,
+            vec![Channel {
+                joint: 0,
+                values: ChannelValues::Translation(Keyframes::new(
+                    vec![0.0, 2.0],
+                    vec![Vector3::new(0.0, 0.0, 0.0), Vector3::new(4.0, 0.0, 0.0)],
+                    Interpolation::Linear,
+                )),
+            }],
+        )
+    }
+
+    #[test]
+    fn looped_samples_wrap_around() {
+        let clip = clip();
+        let mut pose = [JointTransform::default()];
+
+        clip.sample_looped(5.0, &mut pose);
+        assert_eq!(pose[0].translation, Vector3::new(2.0, 0.0, 0.0));
+
+        clip.sample_looped(-0.5, &mut pose);
+        assert_eq!(pose[0].translation, Vector3::new(3.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn channels_outside_of_the_pose_are_ignored() {
+        let mut clip = clip();
+        clip.channels[0].joint = 3;
+
+        let mut pose = [JointTransform::default()];
+        clip.sample(1.0, &mut pose);
+
+        assert_eq!(pose[0], JointTransform::default());
+    }
+}
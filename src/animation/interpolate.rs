@@ -0,0 +1,89 @@
+use cgmath::{InnerSpace, Quaternion, Vector3, VectorSpace};
+
+/// A value that can be animated by [`Keyframes`](super::Keyframes).
+pub trait Interpolate: Copy {
+    /// `amount` is between 0 (`self`) and 1 (`other`).
+    fn lerp(self, other: Self, amount: f32) -> Self;
+
+    /// The cubic Hermite spline from `self` to `other`. The tangents are per second, `duration` is the time between the
+    /// keyframes.
+    fn hermite(
+        self,
+        out_tangent: Self,
+        other: Self,
+        in_tangent: Self,
+        amount: f32,
+        duration: f32,
+    ) -> Self;
+}
+
+/// The factors of the start value, out-tangent, end value and in-tangent of a cubic Hermite spline.
+fn hermite_factors(t: f32, duration: f32) -> [f32; 4] {
+    let (t2, t3) = (t * t, t * t * t);
+
+    [
+        2.0 * t3 - 3.0 * t2 + 1.0,
+        (t3 - 2.0 * t2 + t) * duration,
+        -2.0 * t3 + 3.0 * t2,
+        (t3 - t2) * duration,
+    ]
+}
+
+/// E.g. the weight of a [`MorphTarget`](crate::mesh::MorphTarget).
+impl Interpolate for f32 {
+    fn lerp(self, other: Self, amount: f32) -> Self {
+        self + (other - self) * amount
+    }
+
+    fn hermite(
+        self,
+        out_tangent: Self,
+        other: Self,
+        in_tangent: Self,
+        amount: f32,
+        duration: f32,
+    ) -> Self {
+        let [a, b, c, d] = hermite_factors(amount, duration);
+
+        self * a + out_tangent * b + other * c + in_tangent * d
+    }
+}
+
+impl Interpolate for Vector3<f32> {
+    fn lerp(self, other: Self, amount: f32) -> Self {
+        VectorSpace::lerp(self, other, amount)
+    }
+
+    fn hermite(
+        self,
+        out_tangent: Self,
+        other: Self,
+        in_tangent: Self,
+        amount: f32,
+        duration: f32,
+    ) -> Self {
+        let [a, b, c, d] = hermite_factors(amount, duration);
+
+        self * a + out_tangent * b + other * c + in_tangent * d
+    }
+}
+
+impl Interpolate for Quaternion<f32> {
+    /// Spherical linear interpolation along the shorter arc.
+    fn lerp(self, other: Self, amount: f32) -> Self {
+        self.slerp(other, amount)
+    }
+
+    fn hermite(
+        self,
+        out_tangent: Self,
+        other: Self,
+        in_tangent: Self,
+        amount: f32,
+        duration: f32,
+    ) -> Self {
+        let [a, b, c, d] = hermite_factors(amount, duration);
+
+        (self * a + out_tangent * b + other * c + in_tangent * d).normalize()
+    }
+}
@@ -0,0 +1,13 @@
+use cgmath::Matrix4;
+
+use crate::{self as sifu_render, Uniform};
+
+/// A skinning matrix, transforms a vertex from bind pose to the animated pose in model space.
+///
+/// Upload them with [`Skeleton::joint_buffer`](super::Skeleton::joint_buffer) and bind the buffer as
+/// `array<JointMatrix>`, the shader prelude has skinning helpers.
+#[derive(Uniform, Debug, Clone, Copy, PartialEq)]
+pub struct JointMatrix {
+    #[raw(f32; 4; 4)]
+    pub matrix: Matrix4<f32>,
+}
@@ -0,0 +1,208 @@
+use super::Interpolate;
+
+/// How values between two keyframes are computed, matching the glTF interpolation modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Keeps the value of the previous keyframe
+    Step,
+    /// Linear for vectors, spherical linear for rotations
+    #[default]
+    Linear,
+    /// Cubic Hermite spline, every keyframe has an in-tangent, a value and an out-tangent
+    CubicSpline,
+}
+
+/// The values of an animated property over time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframes<T> {
+    /// In seconds, ascending
+    times: Vec<f32>,
+    /// One value per time, or an in-tangent, a value and an out-tangent per time for [`Interpolation::CubicSpline`]
+    values: Vec<T>,
+    interpolation: Interpolation,
+}
+
+impl<T: Interpolate> Keyframes<T> {
+    /// # Panics
+    /// If the number of values doesn't match the number of times, or the times are not ascending.
+    pub fn new(times: Vec<f32>, values: Vec<T>, interpolation: Interpolation) -> Self {
+        let values_per_time = match interpolation {
+            Interpolation::CubicSpline => 3,
+            Interpolation::Step | Interpolation::Linear => 1,
+        };
+
+        assert_eq!(
+            times.len() * values_per_time,
+            values.len(),
+            "expected {values_per_time} values per keyframe for {interpolation:?} interpolation"
+        );
+        assert!(
+            times.windows(2).all(|pair| pair[0] <= pair[1]),
+            "keyframe times have to be ascending"
+        );
+
+        Self {
+            times,
+            values,
+            interpolation,
+        }
+    }
+
+    pub fn times(&self) -> &[f32] {
+        &self.times
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.times.last().copied().unwrap_or(0.0)
+    }
+
+    /// The value at `time`, clamped to the first and last keyframe. `None` if there are no keyframes.
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let next = self.times.partition_point(|keyframe| *keyframe <= time);
+
+        if next == 0 {
+            return self.times.first().map(|_| self.value(0));
+        }
+
+        if next == self.times.len() {
+            return Some(self.value(next - 1));
+        }
+
+        let previous = next - 1;
+        let delta = self.times[next] - self.times[previous];
+        let amount = (time - self.times[previous]) / delta;
+
+        Some(match self.interpolation {
+            Interpolation::Step => self.value(previous),
+            Interpolation::Linear => self.value(previous).lerp(self.value(next), amount),
+            Interpolation::CubicSpline => {
+                let out_tangent = self.values[previous * 3 + 2];
+                let in_tangent = self.values[next * 3];
+
+                self.value(previous).hermite(
+                    out_tangent,
+                    self.value(next),
+                    in_tangent,
+                    amount,
+                    delta,
+                )
+            }
+        })
+    }
+
+    /// The value of the keyframe at `idx`, skipping the tangents of cubic splines.
+    fn value(&self, idx: usize) -> T {
+        match self.interpolation {
+            Interpolation::CubicSpline => self.values[idx * 3 + 1],
+            Interpolation::Step | Interpolation::Linear => self.values[idx],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Deg, Quaternion, Rotation3, Vector3, assert_abs_diff_eq};
+
+    use super::{Interpolation, Keyframes};
+
+    #[test]
+    fn empty_keyframes_have_no_value() {
+        let keyframes = Keyframes::<f32>::new(Vec::new(), Vec::new(), Interpolation::Linear);
+
+        assert_eq!(keyframes.sample(1.0), None);
+        assert_eq!(keyframes.duration(), 0.0);
+    }
+
+    #[test]
+    fn samples_are_clamped() {
+        let keyframes = Keyframes::new(vec![1.0, 2.0], vec![3.0, 5.0], Interpolation::Linear);
+
+        assert_eq!(keyframes.sample(0.0), Some(3.0));
+        assert_eq!(keyframes.sample(1.0), Some(3.0));
+        assert_eq!(keyframes.sample(2.0), Some(5.0));
+        assert_eq!(keyframes.sample(10.0), Some(5.0));
+    }
+
+    #[test]
+    fn step_keeps_the_previous_value() {
+        let keyframes = Keyframes::new(vec![0.0, 1.0], vec![3.0, 5.0], Interpolation::Step);
+
+        assert_eq!(keyframes.sample(0.99), Some(3.0));
+        assert_eq!(keyframes.sample(1.0), Some(5.0));
+    }
+
+    #[test]
+    fn linear_interpolates_vectors() {
+        let keyframes = Keyframes::new(
+            vec![0.0, 2.0],
+            vec![Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 4.0, 6.0)],
+            Interpolation::Linear,
+        );
+
+        assert_abs_diff_eq!(keyframes.sample(0.5).unwrap(), Vector3::new(0.5, 1.0, 1.5));
+    }
+
+    #[test]
+    fn linear_slerps_rotations() {
+        let keyframes = Keyframes::new(
+            vec![0.0, 1.0],
+            vec![
+                Quaternion::from_angle_z(Deg(0.0)),
+                Quaternion::from_angle_z(Deg(90.0)),
+            ],
+            Interpolation::Linear,
+        );
+
+        assert_abs_diff_eq!(
+            keyframes.sample(0.5).unwrap(),
+            Quaternion::from_angle_z(Deg(45.0)),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn cubic_spline_uses_the_out_and_in_tangent() {
+        // In-tangent, value and out-tangent per keyframe, the outer tangents must not be used
+        let keyframes = Keyframes::new(
+            vec![0.0, 2.0],
+            vec![100.0, 0.0, 1.0, 0.5, 4.0, 100.0],
+            Interpolation::CubicSpline,
+        );
+
+        assert_eq!(keyframes.sample(0.0), Some(0.0));
+        assert_eq!(keyframes.sample(2.0), Some(4.0));
+
+        // 0 * 0.5 + 1 * 0.25 + 4 * 0.5 + 0.5 * -0.25 with tangents scaled by the duration of 2
+        assert_abs_diff_eq!(keyframes.sample(1.0).unwrap(), 2.125, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn equal_times_jump_to_the_later_keyframe() {
+        let keyframes = Keyframes::new(
+            vec![0.0, 1.0, 1.0, 2.0],
+            vec![0.0, 10.0, 20.0, 30.0],
+            Interpolation::Linear,
+        );
+
+        assert_eq!(keyframes.sample(0.5), Some(5.0));
+        assert_eq!(keyframes.sample(1.0), Some(20.0));
+        assert_eq!(keyframes.sample(1.5), Some(25.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 3 values per keyframe")]
+    fn cubic_spline_needs_tangents() {
+        Keyframes::new(vec![0.0, 1.0], vec![0.0, 1.0], Interpolation::CubicSpline);
+    }
+
+    #[test]
+    #[should_panic(expected = "ascending")]
+    fn times_must_ascend() {
+        Keyframes::new(vec![1.0, 0.0], vec![0.0, 1.0], Interpolation::Linear);
+    }
+}
@@ -0,0 +1,13 @@
+mod animation_clip;
+mod interpolate;
+mod joint_matrix;
+mod joint_transform;
+mod keyframes;
+mod skeleton;
+
+pub use animation_clip::{AnimationClip, Channel, ChannelValues};
+pub use interpolate::Interpolate;
+pub use joint_matrix::JointMatrix;
+pub use joint_transform::JointTransform;
+pub use keyframes::{Interpolation, Keyframes};
+pub use skeleton::{Joint, Skeleton};
@@ -0,0 +1,169 @@
+use cgmath::{Matrix4, SquareMatrix};
+use wgpu::Device;
+
+use crate::{GpuBuffer, StorageBuffer};
+
+use super::{JointMatrix, JointTransform};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Joint {
+    pub name: Option<String>,
+    /// An index into [`Skeleton::joints`], parents come before their children
+    pub parent: Option<usize>,
+    /// The transform relative to the parent when no animation is applied
+    pub rest: JointTransform,
+    /// Transforms from model space into the space of the joint in bind pose
+    pub inverse_bind_matrix: Matrix4<f32>,
+}
+
+/// A joint hierarchy that deforms the vertices of skinned meshes.
+///
+/// A pose is one [`JointTransform`] per joint, start with [`Skeleton::rest_pose`] and let an
+/// [`AnimationClip`](super::AnimationClip) overwrite the animated joints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skeleton {
+    joints: Vec<Joint>,
+}
+
+impl Skeleton {
+    /// # Panics
+    /// If a joint doesn't come after its parent.
+    pub fn new(joints: Vec<Joint>) -> Self {
+        for (idx, joint) in joints.iter().enumerate() {
+            if let Some(parent) = joint.parent {
+                assert!(parent < idx, "joint {idx} comes before its parent {parent}");
+            }
+        }
+
+        Self { joints }
+    }
+
+    pub fn joints(&self) -> &[Joint] {
+        &self.joints
+    }
+
+    /// The index of the first joint called `name`.
+    pub fn joint(&self, name: &str) -> Option<usize> {
+        self.joints
+            .iter()
+            .position(|joint| joint.name.as_deref() == Some(name))
+    }
+
+    pub fn rest_pose(&self) -> Vec<JointTransform> {
+        self.joints.iter().map(|joint| joint.rest).collect()
+    }
+
+    /// The model space transform of every joint in `pose`.
+    ///
+    /// # Panics
+    /// If `pose` has fewer transforms than the skeleton has joints.
+    pub fn global_transforms(&self, pose: &[JointTransform]) -> Vec<Matrix4<f32>> {
+        let mut transforms = Vec::<Matrix4<f32>>::with_capacity(self.joints.len());
+
+        for (joint, local) in self.joints.iter().zip(&pose[..self.joints.len()]) {
+            let parent = joint
+                .parent
+                .map_or(Matrix4::identity(), |parent| transforms[parent]);
+
+            transforms.push(parent * local.matrix());
+        }
+
+        transforms
+    }
+
+    /// The skinning matrix of every joint in `pose`.
+    pub fn joint_matrices(&self, pose: &[JointTransform]) -> Vec<JointMatrix> {
+        self.global_transforms(pose)
+            .into_iter()
+            .zip(&self.joints)
+            .map(|(global, joint)| JointMatrix {
+                matrix: global * joint.inverse_bind_matrix,
+            })
+            .collect()
+    }
+
+    /// Uploads the skinning matrices of `pose`, update it with [`Skeleton::joint_matrices`] every frame.
+    pub fn joint_buffer(
+        &self,
+        device: &Device,
+        pose: &[JointTransform],
+        label: Option<&str>,
+    ) -> StorageBuffer<JointMatrix> {
+        GpuBuffer::storage(device, self.joint_matrices(pose), label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{
+        Deg, Matrix4, Quaternion, Rotation3, SquareMatrix, Vector3, Vector4, assert_abs_diff_eq,
+    };
+
+    use super::{Joint, Skeleton};
+    use crate::animation::JointTransform;
+
+    fn joint(
+        parent: Option<usize>,
+        translation: Vector3<f32>,
+        bind_position: Vector3<f32>,
+    ) -> Joint {
+        Joint {
+            name: None,
+            parent,
+            rest: JointTransform {
+                translation,
+                ..Default::default()
+            },
+            inverse_bind_matrix: Matrix4::from_translation(-bind_position),
+        }
+    }
+
+    #[test]
+    fn parents_are_applied_before_children() {
+        let skeleton = Skeleton::new(vec![
+            joint(None, Vector3::unit_x(), Vector3::unit_x()),
+            joint(Some(0), Vector3::unit_x(), Vector3::unit_x() * 2.0),
+            joint(Some(1), Vector3::unit_x(), Vector3::unit_x() * 3.0),
+        ]);
+
+        let transforms = skeleton.global_transforms(&skeleton.rest_pose());
+
+        for (idx, transform) in transforms.iter().enumerate() {
+            assert_abs_diff_eq!(transform.w.truncate(), Vector3::unit_x() * (idx + 1) as f32);
+        }
+
+        // The rest pose matches the bind pose
+        for matrix in skeleton.joint_matrices(&skeleton.rest_pose()) {
+            assert_abs_diff_eq!(matrix.matrix, Matrix4::identity());
+        }
+    }
+
+    #[test]
+    fn joint_matrices_move_vertices_with_their_joint() {
+        let skeleton = Skeleton::new(vec![
+            joint(None, Vector3::unit_x(), Vector3::unit_x()),
+            joint(Some(0), Vector3::unit_x(), Vector3::unit_x() * 2.0),
+        ]);
+
+        let mut pose = skeleton.rest_pose();
+        pose[0].rotation = Quaternion::from_angle_z(Deg(90.0));
+
+        let matrices = skeleton.joint_matrices(&pose);
+
+        // The root rotates around (1, 0, 0), the child is carried along
+        let root = matrices[0].matrix * Vector4::new(2.0, 0.0, 0.0, 1.0);
+        let child = matrices[1].matrix * Vector4::new(3.0, 0.0, 0.0, 1.0);
+
+        assert_abs_diff_eq!(root, Vector4::new(1.0, 1.0, 0.0, 1.0), epsilon = 1e-6);
+        assert_abs_diff_eq!(child, Vector4::new(1.0, 2.0, 0.0, 1.0), epsilon = 1e-6);
+    }
+
+    #[test]
+    #[should_panic(expected = "comes before its parent")]
+    fn children_must_come_after_their_parent() {
+        Skeleton::new(vec![
+            joint(Some(1), Vector3::unit_x(), Vector3::unit_x()),
+            joint(None, Vector3::unit_x(), Vector3::unit_x()),
+        ]);
+    }
+}
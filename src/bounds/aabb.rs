@@ -0,0 +1,108 @@
+use cgmath::{ElementWise, Matrix4, Vector3};
+
+/// An axis aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vector3<f32>,
+    pub max: Vector3<f32>,
+}
+
+impl Aabb {
+    pub fn new(min: Vector3<f32>, max: Vector3<f32>) -> Self {
+        Self { min, max }
+    }
+
+    /// `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Vector3<f32>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Self::new(first, first), |aabb, point| Self {
+            min: aabb.min.zip(point, f32::min),
+            max: aabb.max.zip(point, f32::max),
+        }))
+    }
+
+    pub fn center(&self) -> Vector3<f32> {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn half_size(&self) -> Vector3<f32> {
+        (self.max - self.min) / 2.0
+    }
+
+    pub fn contains(&self, point: Vector3<f32>) -> bool {
+        (0..3).all(|axis| self.min[axis] <= point[axis] && point[axis] <= self.max[axis])
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self {
+            min: self.min.zip(other.min, f32::min),
+            max: self.max.zip(other.max, f32::max),
+        }
+    }
+
+    /// The smallest axis aligned box containing this box after the affine `transform`.
+    pub fn transform(&self, transform: &Matrix4<f32>) -> Self {
+        let center = transform * self.center().extend(1.0);
+        let half_size = self.half_size();
+
+        // Every axis of the transformed box is the sum of the absolute transformed half sizes
+        let extent = [transform.x, transform.y, transform.z]
+            .iter()
+            .zip([half_size.x, half_size.y, half_size.z])
+            .map(|(column, half_size)| column.truncate().map(f32::abs) * half_size)
+            .fold(Vector3::new(0.0, 0.0, 0.0), |sum, extent| {
+                sum.add_element_wise(extent)
+            });
+
+        Self {
+            min: center.truncate() - extent,
+            max: center.truncate() + extent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Deg, Matrix4, Vector3, assert_abs_diff_eq};
+
+    use super::Aabb;
+
+    #[test]
+    fn rotated_boxes_grow() {
+        let aabb = Aabb::new(Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+        let rotated = aabb.transform(&Matrix4::from_angle_z(Deg(45.0)));
+
+        let extent = Vector3::new(2.0f32.sqrt(), 2.0f32.sqrt(), 1.0);
+        assert_abs_diff_eq!(rotated.min, -extent, epsilon = 1e-6);
+        assert_abs_diff_eq!(rotated.max, extent, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn transforms_rotate_around_the_origin() {
+        let aabb = Aabb::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 1.0, 1.0));
+        let transform = Matrix4::from_translation(Vector3::new(0.0, 0.0, 5.0))
+            * Matrix4::from_angle_z(Deg(90.0));
+
+        let transformed = aabb.transform(&transform);
+
+        assert_abs_diff_eq!(
+            transformed.min,
+            Vector3::new(-1.0, 0.0, 5.0),
+            epsilon = 1e-6
+        );
+        assert_abs_diff_eq!(transformed.max, Vector3::new(0.0, 2.0, 6.0), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn from_points() {
+        let aabb = Aabb::from_points([Vector3::new(1.0, -2.0, 3.0), Vector3::new(-1.0, 2.0, 0.0)])
+            .unwrap();
+
+        assert_eq!(aabb.min, Vector3::new(-1.0, -2.0, 0.0));
+        assert_eq!(aabb.max, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(Aabb::from_points([]), None);
+    }
+}
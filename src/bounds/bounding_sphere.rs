@@ -0,0 +1,118 @@
+use cgmath::{InnerSpace, Matrix4, Vector3};
+
+use super::Aabb;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingSphere {
+    pub center: Vector3<f32>,
+    pub radius: f32,
+}
+
+impl BoundingSphere {
+    pub fn new(center: Vector3<f32>, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// A sphere close to the smallest one containing all `points`, `None` if there are no points.
+    ///
+    /// Uses Ritter's algorithm, or the sphere around the bounding box if that is smaller.
+    pub fn from_points(points: &[Vector3<f32>]) -> Option<Self> {
+        let first = *points.first()?;
+
+        let farthest_from = |from: Vector3<f32>| {
+            points
+                .iter()
+                .copied()
+                .max_by(|a, b| (a - from).magnitude2().total_cmp(&(b - from).magnitude2()))
+                .unwrap_or(from)
+        };
+
+        let a = farthest_from(first);
+        let b = farthest_from(a);
+
+        let mut sphere = Self::new((a + b) / 2.0, (b - a).magnitude() / 2.0);
+
+        // Grows the sphere just enough to contain every point outside of it
+        for point in points {
+            let distance = (point - sphere.center).magnitude();
+
+            if distance > sphere.radius {
+                let radius = (sphere.radius + distance) / 2.0;
+                sphere.center += (point - sphere.center) * ((radius - sphere.radius) / distance);
+                sphere.radius = radius;
+            }
+        }
+
+        let aabb = Aabb::from_points(points.iter().copied())?;
+        let aabb_sphere = Self::new(
+            aabb.center(),
+            points
+                .iter()
+                .map(|point| (point - aabb.center()).magnitude())
+                .fold(0.0, f32::max),
+        );
+
+        match aabb_sphere.radius < sphere.radius {
+            true => Some(aabb_sphere),
+            false => Some(sphere),
+        }
+    }
+
+    pub fn contains(&self, point: Vector3<f32>) -> bool {
+        (point - self.center).magnitude2() <= self.radius * self.radius
+    }
+
+    /// A sphere containing this sphere after the affine `transform`, non-uniform scales use the largest axis.
+    pub fn transform(&self, transform: &Matrix4<f32>) -> Self {
+        let scale = [transform.x, transform.y, transform.z]
+            .iter()
+            .map(|column| column.truncate().magnitude())
+            .fold(0.0, f32::max);
+
+        Self {
+            center: (transform * self.center.extend(1.0)).truncate(),
+            radius: self.radius * scale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{InnerSpace, Vector3};
+
+    use super::BoundingSphere;
+
+    /// Points spread unevenly in a box, from a linear congruential generator.
+    fn points() -> Vec<Vector3<f32>> {
+        let mut state = 12345u32;
+        let mut next = || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+
+        (0..500)
+            .map(|_| Vector3::new(next() * 4.0 - 1.0, next() * next(), next() * 2.0))
+            .collect()
+    }
+
+    #[test]
+    fn contains_every_point() {
+        let points = points();
+        let sphere = BoundingSphere::from_points(&points).unwrap();
+
+        for point in points {
+            assert!((point - sphere.center).magnitude() <= sphere.radius * (1.0 + 1e-5));
+        }
+    }
+
+    #[test]
+    fn single_point() {
+        let point = Vector3::new(1.0, 2.0, 3.0);
+
+        assert_eq!(
+            BoundingSphere::from_points(&[point]),
+            Some(BoundingSphere::new(point, 0.0))
+        );
+        assert_eq!(BoundingSphere::from_points(&[]), None);
+    }
+}
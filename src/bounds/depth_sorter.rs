@@ -0,0 +1,76 @@
+use cgmath::{InnerSpace, Vector3};
+
+use crate::mesh::MeshInstance;
+
+/// Sorts transparent instances back to front before they are written to an instance buffer, so alpha blending
+/// composites them correctly.
+///
+/// ```ignore
+/// let sorter = camera.depth_sorter();
+/// sorter.sort(&mut instances);
+/// instance_buffer.update(device, queue, instances);
+///
+/// // Draw meshes with transparent instances back to front as well
+/// sorter.sort_by(&mut meshes, |(_, position)| *position);
+/// ```
+///
+/// The sort is stable, instances at the same depth keep their order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthSorter {
+    camera_position: Vector3<f32>,
+    /// `None` sorts by the distance to the camera instead of the view space depth
+    view_direction: Option<Vector3<f32>>,
+}
+
+impl DepthSorter {
+    /// Sorts by the distance to the camera, which works for any view direction and field of view.
+    pub fn new(camera_position: Vector3<f32>) -> Self {
+        Self {
+            camera_position,
+            view_direction: None,
+        }
+    }
+
+    /// Sorts by the view space depth along `view_direction`, which matches the depth buffer of planar projections.
+    pub fn with_view_direction(self, view_direction: Vector3<f32>) -> Self {
+        Self {
+            view_direction: Some(view_direction.normalize()),
+            ..self
+        }
+    }
+
+    pub fn camera_position(&self) -> Vector3<f32> {
+        self.camera_position
+    }
+
+    /// Larger values are further away. Only comparable to other depths of the same sorter.
+    pub fn depth(&self, position: Vector3<f32>) -> f32 {
+        let offset = position - self.camera_position;
+
+        match self.view_direction {
+            Some(view_direction) => offset.dot(view_direction),
+            None => offset.magnitude2(),
+        }
+    }
+
+    /// Sorts the instances by their [`MeshInstance::position`] from back to front.
+    ///
+    /// Instances without a position are treated as infinitely far away, they are drawn first.
+    pub fn sort<I: MeshInstance>(&self, instances: &mut [I]) {
+        self.sort_by_depth(instances, |instance| {
+            instance
+                .position()
+                .map_or(f32::INFINITY, |position| self.depth(position))
+        });
+    }
+
+    /// Sorts anything with a world space position from back to front, e.g. whole meshes by the center of their
+    /// instances.
+    pub fn sort_by<T>(&self, items: &mut [T], position: impl Fn(&T) -> Vector3<f32>) {
+        self.sort_by_depth(items, |item| self.depth(position(item)));
+    }
+
+    fn sort_by_depth<T>(&self, items: &mut [T], depth: impl Fn(&T) -> f32) {
+        items.sort_by(|a, b| depth(b).total_cmp(&depth(a)));
+    }
+}
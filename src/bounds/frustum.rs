@@ -0,0 +1,159 @@
+use cgmath::{InnerSpace, Matrix, Matrix4, Vector3, Vector4};
+
+use super::{Aabb, BoundingSphere, MeshBounds};
+
+/// The six planes of a view frustum, the normals point inwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    /// `xyz` is the normalized normal, `w` the distance, a point `p` is inside if `dot(xyz, p) + w >= 0`.
+    ///
+    /// Left, right, bottom, top, near and far. The far plane of an infinite projection never culls anything.
+    pub planes: [Vector4<f32>; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes from a view projection matrix with wgpu's clip space depth range of `0..=1`.
+    ///
+    /// Works for every [`DepthMode`](crate::texture::DepthMode), reversed depth just swaps the near and far plane.
+    pub fn from_view_proj(view_proj: Matrix4<f32>) -> Self {
+        let rows = [0, 1, 2, 3].map(|row| view_proj.row(row));
+
+        let planes = [
+            rows[3] + rows[0],
+            rows[3] - rows[0],
+            rows[3] + rows[1],
+            rows[3] - rows[1],
+            rows[2],
+            rows[3] - rows[2],
+        ]
+        .map(|plane| {
+            let length = plane.truncate().magnitude();
+
+            match length > 0.0 {
+                true => plane / length,
+                false => plane,
+            }
+        });
+
+        Self { planes }
+    }
+
+    pub fn contains_point(&self, point: Vector3<f32>) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.truncate().dot(point) + plane.w >= 0.0)
+    }
+
+    /// Conservative, spheres close to the corners can intersect although they are outside.
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.truncate().dot(sphere.center) + plane.w >= -sphere.radius)
+    }
+
+    /// Conservative, boxes close to the corners can intersect although they are outside.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            // The corner farthest along the normal
+            let corner = Vector3::new(
+                if plane.x >= 0.0 {
+                    aabb.max.x
+                } else {
+                    aabb.min.x
+                },
+                if plane.y >= 0.0 {
+                    aabb.max.y
+                } else {
+                    aabb.min.y
+                },
+                if plane.z >= 0.0 {
+                    aabb.max.z
+                } else {
+                    aabb.min.z
+                },
+            );
+
+            plane.truncate().dot(corner) + plane.w >= 0.0
+        })
+    }
+
+    /// Tests the sphere first and the box only if the sphere intersects.
+    pub fn intersects(&self, bounds: &MeshBounds) -> bool {
+        self.intersects_sphere(&bounds.sphere) && self.intersects_aabb(&bounds.aabb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Deg, Vector3};
+
+    use super::Frustum;
+    use crate::{bounds::BoundingSphere, sample::camera_uniform::Projection};
+
+    /// A camera at the origin looking along `-z`.
+    fn frustum(projection: Projection) -> Frustum {
+        Frustum::from_view_proj(projection.matrix(Deg(90.0).into(), 1.0))
+    }
+
+    fn projections() -> [Projection; 3] {
+        [
+            Projection::Standard {
+                near: 0.1,
+                far: 100.0,
+            },
+            Projection::ReverseZ {
+                near: 0.1,
+                far: 100.0,
+            },
+            Projection::ReverseZInfinite { near: 0.1 },
+        ]
+    }
+
+    #[test]
+    fn points_between_the_planes_are_inside() {
+        for projection in projections() {
+            let frustum = frustum(projection);
+
+            assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -0.15)));
+            assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -1.0)));
+            assert!(frustum.contains_point(Vector3::new(0.9, -0.9, -1.0)));
+            assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -99.0)));
+
+            assert!(!frustum.contains_point(Vector3::new(1.1, 0.0, -1.0)));
+            assert!(!frustum.contains_point(Vector3::new(0.0, -1.1, -1.0)));
+            assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -0.05)));
+            assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, 1.0)));
+        }
+    }
+
+    #[test]
+    fn far_planes_cull() {
+        for projection in &projections()[..2] {
+            let frustum = frustum(*projection);
+
+            assert!(!frustum.contains_point(Vector3::new(0.0, 0.0, -101.0)));
+            assert!(
+                !frustum
+                    .intersects_sphere(&BoundingSphere::new(Vector3::new(0.0, 0.0, -110.0), 5.0))
+            );
+        }
+    }
+
+    #[test]
+    fn infinite_far_planes_never_cull() {
+        let frustum = frustum(Projection::ReverseZInfinite { near: 0.1 });
+
+        assert!(frustum.contains_point(Vector3::new(0.0, 0.0, -1e30)));
+        assert!(
+            frustum.intersects_sphere(&BoundingSphere::new(Vector3::new(0.0, 0.0, -1e20), 1.0))
+        );
+    }
+
+    #[test]
+    fn spheres_touching_a_plane_intersect() {
+        let frustum = frustum(projections()[0]);
+
+        assert!(frustum.intersects_sphere(&BoundingSphere::new(Vector3::new(0.0, 0.0, 1.0), 1.5)));
+        assert!(!frustum.intersects_sphere(&BoundingSphere::new(Vector3::new(0.0, 0.0, 1.0), 0.5)));
+    }
+}
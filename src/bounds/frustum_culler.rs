@@ -0,0 +1,140 @@
+use cgmath::Matrix4;
+
+use super::{Frustum, MeshBounds};
+
+/// Filters instances outside of a [`Frustum`] before they are written to an instance buffer.
+///
+/// ```ignore
+/// let mut culler = FrustumCuller::new(camera.uniform().frustum());
+/// let visible = culler.cull(&bounds, instances, |instance| instance.mat);
+/// instance_buffer.update(device, queue, visible);
+/// println!("{} of {} visible", culler.stats().visible, culler.stats().total);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrustumCuller {
+    frustum: Frustum,
+    stats: CullStats,
+}
+
+/// The instance counts of all [`FrustumCuller::cull`] calls since the last reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CullStats {
+    pub total: u32,
+    pub visible: u32,
+}
+
+impl CullStats {
+    pub fn culled(&self) -> u32 {
+        self.total - self.visible
+    }
+}
+
+impl FrustumCuller {
+    pub fn new(frustum: Frustum) -> Self {
+        Self {
+            frustum,
+            stats: CullStats::default(),
+        }
+    }
+
+    pub fn frustum(&self) -> &Frustum {
+        &self.frustum
+    }
+
+    /// Sets the frustum for a new frame and resets the stats.
+    pub fn set_frustum(&mut self, frustum: Frustum) {
+        self.frustum = frustum;
+        self.reset_stats();
+    }
+
+    pub fn stats(&self) -> CullStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = CullStats::default();
+    }
+
+    /// Keeps the instances whose `bounds`, transformed by the matrix `model` returns, intersect the frustum.
+    ///
+    /// The stats are updated while the returned iterator is consumed.
+    pub fn cull<'a, I: 'a>(
+        &'a mut self,
+        bounds: &'a MeshBounds,
+        instances: impl IntoIterator<Item = I> + 'a,
+        model: impl Fn(&I) -> Matrix4<f32> + 'a,
+    ) -> impl Iterator<Item = I> + 'a {
+        instances.into_iter().filter(move |instance| {
+            let visible = self.frustum.intersects(&bounds.transform(&model(instance)));
+
+            self.stats.total += 1;
+            self.stats.visible += visible as u32;
+
+            visible
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Deg, Matrix4, Vector3};
+
+    use super::{CullStats, FrustumCuller};
+    use crate::{
+        bounds::{Frustum, MeshBounds},
+        sample::camera_uniform::Projection,
+    };
+
+    fn frustum() -> Frustum {
+        let projection = Projection::Standard {
+            near: 0.1,
+            far: 100.0,
+        };
+
+        Frustum::from_view_proj(projection.matrix(Deg(90.0).into(), 1.0))
+    }
+
+    #[test]
+    fn counts_visible_and_culled_instances() {
+        let bounds = MeshBounds::from_positions(&[
+            Vector3::new(-0.5, -0.5, -0.5),
+            Vector3::new(0.5, 0.5, 0.5),
+        ])
+        .unwrap();
+
+        let instances = [
+            Vector3::new(0.0, 0.0, -5.0),
+            Vector3::new(0.0, 0.0, 5.0),
+            Vector3::new(20.0, 0.0, -5.0),
+            Vector3::new(5.0, 0.0, -5.0),
+        ];
+
+        let mut culler = FrustumCuller::new(frustum());
+        let visible = culler
+            .cull(&bounds, instances, |position| {
+                Matrix4::from_translation(*position)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(visible, [instances[0], instances[3]]);
+        assert_eq!(
+            culler.stats(),
+            CullStats {
+                total: 4,
+                visible: 2
+            }
+        );
+        assert_eq!(culler.stats().culled(), 2);
+
+        // Stats add up until they are reset
+        culler
+            .cull(&bounds, instances, |position| {
+                Matrix4::from_translation(*position)
+            })
+            .for_each(drop);
+        assert_eq!(culler.stats().total, 8);
+
+        culler.set_frustum(frustum());
+        assert_eq!(culler.stats(), CullStats::default());
+    }
+}
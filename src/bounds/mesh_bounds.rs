@@ -0,0 +1,44 @@
+use cgmath::{Matrix4, Vector3};
+
+use crate::mesh::{MeshAttributes, VertexAttributes};
+
+use super::{Aabb, BoundingSphere};
+
+/// The bounding volumes of a mesh in model space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshBounds {
+    pub aabb: Aabb,
+    pub sphere: BoundingSphere,
+}
+
+impl MeshBounds {
+    /// `None` if there are no positions.
+    pub fn from_positions(positions: &[Vector3<f32>]) -> Option<Self> {
+        Some(Self {
+            aabb: Aabb::from_points(positions.iter().copied())?,
+            sphere: BoundingSphere::from_points(positions)?,
+        })
+    }
+
+    /// `None` if there are no vertices.
+    pub fn from_attributes(vertices: &[MeshAttributes]) -> Option<Self> {
+        Self::from_vertices(vertices)
+    }
+
+    /// `None` if there are no vertices.
+    pub fn from_vertices<V: VertexAttributes>(vertices: &[V]) -> Option<Self> {
+        let positions = vertices
+            .iter()
+            .map(VertexAttributes::position)
+            .collect::<Vec<_>>();
+
+        Self::from_positions(&positions)
+    }
+
+    pub fn transform(&self, transform: &Matrix4<f32>) -> Self {
+        Self {
+            aabb: self.aabb.transform(transform),
+            sphere: self.sphere.transform(transform),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+mod aabb;
+mod bounding_sphere;
+mod depth_sorter;
+mod frustum;
+mod frustum_culler;
+mod mesh_bounds;
+
+pub use aabb::Aabb;
+pub use bounding_sphere::BoundingSphere;
+pub use depth_sorter::DepthSorter;
+pub use frustum::Frustum;
+pub use frustum_culler::{CullStats, FrustumCuller};
+pub use mesh_bounds::MeshBounds;
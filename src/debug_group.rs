@@ -0,0 +1,73 @@
+use std::ops::{Deref, DerefMut};
+
+use wgpu::{CommandEncoder, ComputePass, RenderPass};
+
+/// Anything that can record debug groups, e.g. [`RenderPass`] and [`CommandEncoder`].
+pub trait DebugGroupExt {
+    fn push_debug_group(&mut self, label: &str);
+    fn pop_debug_group(&mut self);
+
+    /// Pushes a debug group that gets popped when the returned guard is dropped.
+    ///
+    /// The guard derefs to `self`, so commands can be recorded through it.
+    fn debug_group(&mut self, label: &str) -> DebugGroup<'_, Self> {
+        self.push_debug_group(label);
+
+        DebugGroup { target: self }
+    }
+}
+
+/// A debug group that is popped on drop, see [`DebugGroupExt::debug_group`].
+pub struct DebugGroup<'g, T: DebugGroupExt + ?Sized> {
+    target: &'g mut T,
+}
+
+impl<T: DebugGroupExt + ?Sized> Deref for DebugGroup<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.target
+    }
+}
+
+impl<T: DebugGroupExt + ?Sized> DerefMut for DebugGroup<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.target
+    }
+}
+
+impl<T: DebugGroupExt + ?Sized> Drop for DebugGroup<'_, T> {
+    fn drop(&mut self) {
+        self.target.pop_debug_group();
+    }
+}
+
+impl DebugGroupExt for RenderPass<'_> {
+    fn push_debug_group(&mut self, label: &str) {
+        RenderPass::push_debug_group(self, label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        RenderPass::pop_debug_group(self);
+    }
+}
+
+impl DebugGroupExt for ComputePass<'_> {
+    fn push_debug_group(&mut self, label: &str) {
+        ComputePass::push_debug_group(self, label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        ComputePass::pop_debug_group(self);
+    }
+}
+
+impl DebugGroupExt for CommandEncoder {
+    fn push_debug_group(&mut self, label: &str) {
+        CommandEncoder::push_debug_group(self, label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        CommandEncoder::pop_debug_group(self);
+    }
+}
@@ -0,0 +1,76 @@
+use cgmath::Vector2;
+use wgpu::{Device, Queue};
+
+use crate::texture::{Color, ImageTexture, PixelFormat};
+
+/// A decoded image of a glTF file, converted to RGBA with 8 bits per channel.
+#[derive(Debug, Clone)]
+pub struct GltfImage {
+    pub size: Vector2<u32>,
+    pub pixels: Vec<Color>,
+}
+
+impl GltfImage {
+    pub(super) fn new(data: ::gltf::image::Data) -> Self {
+        use ::gltf::image::Format;
+
+        // Bytes per channel and number of channels
+        let (channel_size, channels) = match data.format {
+            Format::R8 => (1, 1),
+            Format::R8G8 => (1, 2),
+            Format::R8G8B8 => (1, 3),
+            Format::R8G8B8A8 => (1, 4),
+            Format::R16 => (2, 1),
+            Format::R16G16 => (2, 2),
+            Format::R16G16B16 => (2, 3),
+            Format::R16G16B16A16 => (2, 4),
+            Format::R32G32B32FLOAT => (4, 3),
+            Format::R32G32B32A32FLOAT => (4, 4),
+        };
+
+        let channel = |bytes: &[u8]| match channel_size {
+            1 => bytes[0],
+            2 => (u16::from_ne_bytes([bytes[0], bytes[1]]) >> 8) as u8,
+            _ => {
+                let value = f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                (value.clamp(0.0, 1.0) * 255.0).round() as u8
+            }
+        };
+
+        let pixels = data
+            .pixels
+            .chunks_exact(channel_size * channels)
+            .map(|pixel| {
+                let values = pixel
+                    .chunks_exact(channel_size)
+                    .map(channel)
+                    .collect::<Vec<_>>();
+
+                match values[..] {
+                    // Grayscale images decoded from PNGs
+                    [l] => Color::new(l, l, l, u8::MAX),
+                    [l, a] => Color::new(l, l, l, a),
+                    [r, g, b] => Color::new(r, g, b, u8::MAX),
+                    [r, g, b, a, ..] => Color::new(r, g, b, a),
+                    [] => unreachable!("pixels have at least one channel"),
+                }
+            })
+            .collect();
+
+        Self {
+            size: Vector2::new(data.width, data.height),
+            pixels,
+        }
+    }
+
+    /// Use [`ColorPixel`](crate::texture::ColorPixel) for color textures and [`Rgba8Pixel`](crate::texture::Rgba8Pixel) for
+    /// linear data like normal, metallic-roughness and occlusion textures.
+    pub fn to_texture<P: PixelFormat<Pixel = Color>>(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        label: Option<&str>,
+    ) -> ImageTexture<P> {
+        ImageTexture::new(device, queue, self.size, &self.pixels, label)
+    }
+}
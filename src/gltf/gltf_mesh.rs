@@ -0,0 +1,300 @@
+use cgmath::{Vector2, Vector3, Vector4};
+use wgpu::Device;
+
+use crate::{
+    bounds::MeshBounds,
+    mesh::{FromMeshAttributes, Mesh, MeshAttributes, MorphDelta, MorphTarget, Submesh, Vertex},
+};
+
+/// A mesh of a glTF file, every primitive has its own material.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfMesh {
+    pub name: Option<String>,
+    pub primitives: Vec<GltfPrimitive>,
+    /// The default weights of the morph targets of the primitives
+    pub weights: Vec<f32>,
+}
+
+/// The triangles of a glTF primitive. Strips and fans are converted to lists, points and lines are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfPrimitive {
+    pub vertices: Vec<MeshAttributes>,
+    pub triangles: Vec<[u32; 3]>,
+    /// An index into [`GltfScene::materials`](super::GltfScene::materials), `None` for the default material
+    pub material: Option<usize>,
+    /// The blend shapes of the primitive, every primitive of a mesh has the same number of them
+    pub morph_targets: Vec<MorphTarget>,
+}
+
+impl GltfMesh {
+    pub(super) fn new(mesh: ::gltf::Mesh, buffers: &[::gltf::buffer::Data]) -> Self {
+        let primitives = mesh
+            .primitives()
+            .filter_map(|primitive| GltfPrimitive::new(primitive, buffers))
+            .collect();
+
+        Self {
+            name: mesh.name().map(String::from),
+            primitives,
+            weights: mesh.weights().map(<[f32]>::to_vec).unwrap_or_default(),
+        }
+    }
+
+    /// Uploads all primitives into a single mesh, the [`Submesh`] at an index draws the primitive at that index.
+    pub fn to_mesh<V: Vertex + FromMeshAttributes>(
+        &self,
+        device: &Device,
+        label: Option<&str>,
+    ) -> Mesh<V> {
+        let vertices = self
+            .primitives
+            .iter()
+            .flat_map(|primitive| primitive.to_vertices::<V>());
+
+        let mut mesh =
+            Mesh::new(device, vertices, self.triangles(), label).with_submeshes(self.submeshes());
+
+        let morph_targets = self.morph_targets();
+
+        if !morph_targets.is_empty() {
+            mesh = mesh.with_morph_targets(device, &morph_targets);
+        }
+
+        let positions = self
+            .primitives
+            .iter()
+            .flat_map(|primitive| &primitive.vertices)
+            .map(|vertex| vertex.position)
+            .collect::<Vec<_>>();
+
+        match MeshBounds::from_positions(&positions) {
+            Some(bounds) => mesh.with_bounds(bounds),
+            None => mesh,
+        }
+    }
+
+    /// The triangles of all primitives, offset to index the vertices of the primitive in [`GltfMesh::to_mesh`].
+    pub fn triangles(&self) -> Vec<[u32; 3]> {
+        let mut base_vertex = 0;
+        let mut triangles = Vec::new();
+
+        for primitive in &self.primitives {
+            triangles.extend(
+                primitive
+                    .triangles
+                    .iter()
+                    .map(|triangle| triangle.map(|idx| idx + base_vertex)),
+            );
+
+            base_vertex += primitive.vertices.len() as u32;
+        }
+
+        triangles
+    }
+
+    /// One submesh per primitive, named after its index, with the index ranges of [`GltfMesh::triangles`].
+    pub fn submeshes(&self) -> Vec<Submesh> {
+        let mut first_index = 0;
+
+        self.primitives
+            .iter()
+            .enumerate()
+            .map(|(idx, primitive)| {
+                let index_count = primitive.triangles.len() as u32 * 3;
+                let submesh = Submesh::new(idx.to_string(), first_index..first_index + index_count);
+
+                first_index += index_count;
+                submesh
+            })
+            .collect()
+    }
+
+    /// The morph targets of all primitives combined, the deltas are in the order of the vertices of
+    /// [`GltfMesh::to_mesh`].
+    pub fn morph_targets(&self) -> Vec<MorphTarget> {
+        let target_count = self
+            .primitives
+            .iter()
+            .map(|primitive| primitive.morph_targets.len())
+            .max()
+            .unwrap_or(0);
+
+        (0..target_count)
+            .map(|idx| {
+                let deltas = self
+                    .primitives
+                    .iter()
+                    .flat_map(|primitive| match primitive.morph_targets.get(idx) {
+                        Some(target) => target.deltas.clone(),
+                        None => vec![MorphDelta::default(); primitive.vertices.len()],
+                    })
+                    .collect();
+
+                MorphTarget::new(idx.to_string(), deltas)
+            })
+            .collect()
+    }
+}
+
+impl GltfPrimitive {
+    fn new(primitive: ::gltf::Primitive, buffers: &[::gltf::buffer::Data]) -> Option<Self> {
+        use ::gltf::mesh::Mode;
+
+        let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(|data| &data[..]));
+
+        let mut vertices = reader
+            .read_positions()?
+            .map(|position| MeshAttributes::new(position.into()))
+            .collect::<Vec<_>>();
+
+        if let Some(normals) = reader.read_normals() {
+            for (vertex, normal) in vertices.iter_mut().zip(normals) {
+                vertex.normal = Some(Vector3::from(normal));
+            }
+        }
+
+        if let Some(uvs) = reader.read_tex_coords(0) {
+            for (vertex, uv) in vertices.iter_mut().zip(uvs.into_f32()) {
+                vertex.uv = Some(Vector2::from(uv));
+            }
+        }
+
+        if let Some(tangents) = reader.read_tangents() {
+            for (vertex, tangent) in vertices.iter_mut().zip(tangents) {
+                vertex.tangent = Some(Vector4::from(tangent));
+            }
+        }
+
+        if let Some(joints) = reader.read_joints(0) {
+            for (vertex, joints) in vertices.iter_mut().zip(joints.into_u16()) {
+                vertex.joints = Some(Vector4::from(joints.map(u32::from)));
+            }
+        }
+
+        if let Some(weights) = reader.read_weights(0) {
+            for (vertex, weights) in vertices.iter_mut().zip(weights.into_f32()) {
+                vertex.weights = Some(Vector4::from(weights));
+            }
+        }
+
+        let morph_targets = reader
+            .read_morph_targets()
+            .enumerate()
+            .map(|(idx, (positions, normals, _))| {
+                let mut deltas = vec![MorphDelta::default(); vertices.len()];
+
+                for (delta, position) in deltas.iter_mut().zip(positions.into_iter().flatten()) {
+                    delta.position = Vector3::from(position);
+                }
+
+                for (delta, normal) in deltas.iter_mut().zip(normals.into_iter().flatten()) {
+                    delta.normal = Vector3::from(normal);
+                }
+
+                MorphTarget::new(idx.to_string(), deltas)
+            })
+            .collect();
+
+        let indices = match reader.read_indices() {
+            Some(indices) => indices.into_u32().collect::<Vec<_>>(),
+            None => (0..vertices.len() as u32).collect(),
+        };
+
+        let triangles = match primitive.mode() {
+            Mode::Triangles => indices
+                .chunks_exact(3)
+                .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+                .collect(),
+            // Every other triangle is flipped to keep the winding order
+            Mode::TriangleStrip => indices
+                .windows(3)
+                .enumerate()
+                .map(|(idx, triangle)| match idx % 2 {
+                    0 => [triangle[0], triangle[1], triangle[2]],
+                    _ => [triangle[1], triangle[0], triangle[2]],
+                })
+                .collect(),
+            Mode::TriangleFan => indices
+                .windows(2)
+                .skip(1)
+                .map(|edge| [indices[0], edge[0], edge[1]])
+                .collect(),
+            Mode::Points | Mode::Lines | Mode::LineLoop | Mode::LineStrip => return None,
+        };
+
+        Some(Self {
+            vertices,
+            triangles,
+            material: primitive.material().index(),
+            morph_targets,
+        })
+    }
+
+    pub fn to_vertices<V: FromMeshAttributes>(&self) -> Vec<V> {
+        self.vertices.iter().map(V::from_mesh_attributes).collect()
+    }
+
+    pub fn to_mesh<V: Vertex + FromMeshAttributes>(
+        &self,
+        device: &Device,
+        label: Option<&str>,
+    ) -> Mesh<V> {
+        let mut mesh = Mesh::new(
+            device,
+            self.to_vertices::<V>(),
+            self.triangles.iter().copied(),
+            label,
+        );
+
+        if !self.morph_targets.is_empty() {
+            mesh = mesh.with_morph_targets(device, &self.morph_targets);
+        }
+
+        match self.bounds() {
+            Some(bounds) => mesh.with_bounds(bounds),
+            None => mesh,
+        }
+    }
+
+    /// The bounds of all vertices, `None` if there are none.
+    pub fn bounds(&self) -> Option<MeshBounds> {
+        MeshBounds::from_attributes(&self.vertices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Vector3;
+
+    use super::*;
+
+    fn primitive(vertex_count: usize, triangles: Vec<[u32; 3]>) -> GltfPrimitive {
+        GltfPrimitive {
+            vertices: vec![MeshAttributes::new(Vector3::new(0.0, 0.0, 0.0)); vertex_count],
+            triangles,
+            material: None,
+            morph_targets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn primitives_are_offset_into_a_single_index_buffer() {
+        let mesh = GltfMesh {
+            name: None,
+            primitives: vec![
+                primitive(3, vec![[0, 1, 2]]),
+                primitive(4, vec![[0, 1, 2], [2, 3, 0]]),
+            ],
+            weights: Vec::new(),
+        };
+
+        assert_eq!(mesh.triangles(), vec![[0, 1, 2], [3, 4, 5], [5, 6, 3]]);
+
+        let submeshes = mesh.submeshes();
+
+        assert_eq!(submeshes.len(), 2);
+        assert_eq!(submeshes[0].indices, 0..3);
+        assert_eq!(submeshes[1].indices, 3..9);
+        assert!(submeshes.iter().all(|submesh| submesh.base_vertex == 0));
+    }
+}
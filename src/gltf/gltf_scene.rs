@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use cgmath::{Matrix4, SquareMatrix};
+use wgpu::Device;
+
+use crate::{
+    gpu_buffer::{GpuBuffer, InstanceBuf},
+    mesh::MeshInstance,
+};
+
+use super::{GltfImage, GltfMaterial, GltfMesh};
+
+/// The content of a `.gltf` or `.glb` file.
+///
+/// External buffers and images are read relative to the file, nothing is fetched over the network.
+#[derive(Debug, Clone)]
+pub struct GltfScene {
+    pub meshes: Vec<GltfMesh>,
+    pub materials: Vec<GltfMaterial>,
+    pub images: Vec<GltfImage>,
+    /// The nodes of the default scene (or the first scene) in depth-first order, parents come before their children
+    pub nodes: Vec<GltfNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfNode {
+    pub name: Option<String>,
+    /// An index into [`GltfScene::meshes`]
+    pub mesh: Option<usize>,
+    /// An index into [`GltfScene::nodes`]
+    pub parent: Option<usize>,
+    /// Relative to the parent
+    pub local_transform: Matrix4<f32>,
+    /// Relative to the scene
+    pub transform: Matrix4<f32>,
+}
+
+impl GltfScene {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ::gltf::Error> {
+        let (document, buffers, images) = ::gltf::import(path)?;
+
+        Ok(Self::new(document, buffers, images))
+    }
+
+    /// Loads a `.glb` file or a `.gltf` file that only uses embedded data.
+    pub fn from_slice(data: &[u8]) -> Result<Self, ::gltf::Error> {
+        let (document, buffers, images) = ::gltf::import_slice(data)?;
+
+        Ok(Self::new(document, buffers, images))
+    }
+
+    fn new(
+        document: ::gltf::Document,
+        buffers: Vec<::gltf::buffer::Data>,
+        images: Vec<::gltf::image::Data>,
+    ) -> Self {
+        let meshes = document
+            .meshes()
+            .map(|mesh| GltfMesh::new(mesh, &buffers))
+            .collect();
+
+        let materials = document.materials().map(GltfMaterial::new).collect();
+
+        let images = images.into_iter().map(GltfImage::new).collect();
+
+        let mut nodes = Vec::new();
+
+        if let Some(scene) = document
+            .default_scene()
+            .or_else(|| document.scenes().next())
+        {
+            for node in scene.nodes() {
+                Self::push_node(&mut nodes, node, None);
+            }
+        }
+
+        Self {
+            meshes,
+            materials,
+            images,
+            nodes,
+        }
+    }
+
+    fn push_node(nodes: &mut Vec<GltfNode>, node: ::gltf::Node, parent: Option<usize>) {
+        let local_transform = Matrix4::from(node.transform().matrix());
+        let parent_transform = parent.map_or(Matrix4::identity(), |parent| nodes[parent].transform);
+
+        nodes.push(GltfNode {
+            name: node.name().map(String::from),
+            mesh: node.mesh().map(|mesh| mesh.index()),
+            parent,
+            local_transform,
+            transform: parent_transform * local_transform,
+        });
+
+        let idx = nodes.len() - 1;
+
+        for child in node.children() {
+            Self::push_node(nodes, child, Some(idx));
+        }
+    }
+
+    /// The transforms of all nodes that reference the mesh at `mesh`.
+    pub fn mesh_transforms(&self, mesh: usize) -> impl Iterator<Item = Matrix4<f32>> + '_ {
+        self.nodes
+            .iter()
+            .filter(move |node| node.mesh == Some(mesh))
+            .map(|node| node.transform)
+    }
+
+    /// One instance per node that references the mesh at `mesh`.
+    ///
+    /// `instance` builds the instance from the node transform, e.g. `|mat| SampleInstance { mat }`.
+    pub fn instances<I: MeshInstance>(
+        &self,
+        device: &Device,
+        mesh: usize,
+        instance: impl Fn(Matrix4<f32>) -> I,
+        label: Option<&str>,
+    ) -> GpuBuffer<I, InstanceBuf> {
+        GpuBuffer::instances(device, self.mesh_transforms(mesh).map(instance), label)
+    }
+}
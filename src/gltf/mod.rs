@@ -0,0 +1,11 @@
+mod gltf_image;
+mod gltf_mesh;
+mod gltf_scene;
+mod pbr_material;
+
+pub use gltf_image::GltfImage;
+pub use gltf_mesh::{GltfMesh, GltfPrimitive};
+pub use gltf_scene::{GltfNode, GltfScene};
+pub use pbr_material::{GltfAlphaMode, GltfMaterial, PbrMaterial};
+
+pub use ::gltf::Error as GltfError;
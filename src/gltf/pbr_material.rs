@@ -0,0 +1,113 @@
+use cgmath::{Vector3, Vector4};
+
+use crate::{self as sifu_render, Uniform};
+
+/// The factors of a glTF metallic-roughness material, can be bound as a uniform.
+#[derive(Uniform, Debug, Clone, Copy, PartialEq)]
+pub struct PbrMaterial {
+    /// Linear RGBA
+    #[raw(f32; 4)]
+    pub base_color: Vector4<f32>,
+    /// Linear RGB
+    #[raw(f32; 3)]
+    pub emissive: Vector3<f32>,
+    pub metallic: f32,
+    pub roughness: f32,
+    pub normal_scale: f32,
+    pub occlusion_strength: f32,
+    /// Fragments with a lower alpha are discarded if the alpha mode is [`GltfAlphaMode::Mask`], 0 otherwise
+    pub alpha_cutoff: f32,
+}
+
+impl Default for PbrMaterial {
+    /// The glTF default material.
+    fn default() -> Self {
+        Self {
+            base_color: Vector4::new(1.0, 1.0, 1.0, 1.0),
+            emissive: Vector3::new(0.0, 0.0, 0.0),
+            metallic: 1.0,
+            roughness: 1.0,
+            normal_scale: 1.0,
+            occlusion_strength: 1.0,
+            alpha_cutoff: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GltfAlphaMode {
+    Opaque,
+    Mask,
+    Blend,
+}
+
+/// A material of a glTF file.
+///
+/// Textures are indices into [`GltfScene::images`](super::GltfScene::images). Texture coordinate sets other than the first are not supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GltfMaterial {
+    pub name: Option<String>,
+    pub pbr: PbrMaterial,
+    pub alpha_mode: GltfAlphaMode,
+    pub double_sided: bool,
+
+    /// sRGB
+    pub base_color_texture: Option<usize>,
+    /// Linear, roughness in the green and metalness in the blue channel
+    pub metallic_roughness_texture: Option<usize>,
+    /// Linear, tangent space
+    pub normal_texture: Option<usize>,
+    /// Linear, occlusion in the red channel
+    pub occlusion_texture: Option<usize>,
+    /// sRGB
+    pub emissive_texture: Option<usize>,
+}
+
+impl GltfMaterial {
+    pub(super) fn new(material: ::gltf::Material) -> Self {
+        let pbr = material.pbr_metallic_roughness();
+
+        let alpha_mode = match material.alpha_mode() {
+            ::gltf::material::AlphaMode::Opaque => GltfAlphaMode::Opaque,
+            ::gltf::material::AlphaMode::Mask => GltfAlphaMode::Mask,
+            ::gltf::material::AlphaMode::Blend => GltfAlphaMode::Blend,
+        };
+
+        let image = |texture: ::gltf::Texture| texture.source().index();
+
+        Self {
+            name: material.name().map(String::from),
+            pbr: PbrMaterial {
+                base_color: pbr.base_color_factor().into(),
+                emissive: material.emissive_factor().into(),
+                metallic: pbr.metallic_factor(),
+                roughness: pbr.roughness_factor(),
+                normal_scale: material
+                    .normal_texture()
+                    .map_or(1.0, |normal| normal.scale()),
+                occlusion_strength: material
+                    .occlusion_texture()
+                    .map_or(1.0, |occlusion| occlusion.strength()),
+                alpha_cutoff: match alpha_mode {
+                    GltfAlphaMode::Mask => material.alpha_cutoff().unwrap_or(0.5),
+                    _ => 0.0,
+                },
+            },
+            alpha_mode,
+            double_sided: material.double_sided(),
+            base_color_texture: pbr.base_color_texture().map(|info| image(info.texture())),
+            metallic_roughness_texture: pbr
+                .metallic_roughness_texture()
+                .map(|info| image(info.texture())),
+            normal_texture: material
+                .normal_texture()
+                .map(|normal| image(normal.texture())),
+            occlusion_texture: material
+                .occlusion_texture()
+                .map(|occlusion| image(occlusion.texture())),
+            emissive_texture: material
+                .emissive_texture()
+                .map(|info| image(info.texture())),
+        }
+    }
+}
@@ -1,14 +1,20 @@
-use std::marker::PhantomData;
+use std::{any::type_name, marker::PhantomData};
 
 use bytemuck::{Pod, Zeroable, cast_slice};
 use wgpu::{
-    Buffer, BufferBinding, BufferSlice, BufferUsages, Device,
+    Buffer, BufferAddress, BufferBinding, BufferDescriptor, BufferSlice, BufferUsages,
+    COPY_BUFFER_ALIGNMENT, CommandEncoderDescriptor, Device, Queue,
     util::{BufferInitDescriptor, DeviceExt},
 };
 
+use crate::memory_stats::MemoryAllocation;
+
 pub struct GpuBuffer<T, B: BufferType> {
     pub(super) buffer: Buffer,
     pub(super) extra: B::Extra,
+    /// `None` if the buffer uses the default label, see [`GpuBuffer::label`]
+    pub(super) label: Option<String>,
+    memory: MemoryAllocation,
     _phantom: PhantomData<(T, B)>,
 }
 
@@ -18,33 +24,229 @@ impl<T, B: BufferType> GpuBuffer<T, B> {
         data: &[D],
         usage: BufferUsages,
         extra: B::Extra,
+        label: Option<&str>,
     ) -> Self {
         let raw_bytes = cast_slice(data);
 
         let buffer = device.create_buffer_init(&BufferInitDescriptor {
-            label: None,
+            label: Some(label.unwrap_or(type_name::<T>())),
             contents: raw_bytes,
             usage,
         });
 
+        Self::from_buffer(buffer, extra, label)
+    }
+
+    /// Creates an uninitialized buffer with space for `capacity` elements of `D`.
+    pub(super) fn new_uninit<D: Pod + Zeroable>(
+        device: &Device,
+        capacity: usize,
+        usage: BufferUsages,
+        extra: B::Extra,
+        label: Option<&str>,
+    ) -> Self {
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: Some(label.unwrap_or(type_name::<T>())),
+            size: Self::byte_size::<D>(capacity),
+            usage,
+            mapped_at_creation: false,
+        });
+
+        Self::from_buffer(buffer, extra, label)
+    }
+
+    fn from_buffer(buffer: Buffer, extra: B::Extra, label: Option<&str>) -> Self {
         Self {
+            memory: MemoryAllocation::buffer::<B>(label, buffer.size()),
+            label: label.map(String::from),
             buffer,
             extra,
             _phantom: PhantomData,
         }
     }
+
+    /// Changes the element type, the buffer and `extra` stay the same.
+    pub(crate) fn cast<U>(self) -> GpuBuffer<U, B> {
+        GpuBuffer {
+            buffer: self.buffer,
+            extra: self.extra,
+            label: self.label,
+            memory: self.memory,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The size of `count` elements of `D`, aligned to [`COPY_BUFFER_ALIGNMENT`].
+    pub(super) fn byte_size<D>(count: usize) -> BufferAddress {
+        ((count * size_of::<D>()) as BufferAddress).next_multiple_of(COPY_BUFFER_ALIGNMENT)
+    }
+
+    /// Writes `data` at the byte `offset`, padding it with zeros to a multiple of [`COPY_BUFFER_ALIGNMENT`].
+    ///
+    /// Only use this if nothing after `data` needs to be preserved.
+    pub(super) fn write_raw<D: Pod>(&self, queue: &Queue, offset: BufferAddress, data: &[D]) {
+        let bytes: &[u8] = cast_slice(data);
+
+        if (bytes.len() as BufferAddress).is_multiple_of(COPY_BUFFER_ALIGNMENT) {
+            queue.write_buffer(&self.buffer, offset, bytes);
+        } else {
+            let mut padded = bytes.to_vec();
+            padded.resize(
+                (bytes.len() as BufferAddress).next_multiple_of(COPY_BUFFER_ALIGNMENT) as usize,
+                0,
+            );
+            queue.write_buffer(&self.buffer, offset, &padded);
+        }
+    }
+
+    /// Overwrites the elements `start..start + data.len()` without touching any other element.
+    ///
+    /// # Panics
+    /// If the written byte range is not aligned to [`COPY_BUFFER_ALIGNMENT`].
+    pub(super) fn write_range_raw<D: Pod>(&self, queue: &Queue, start: usize, data: &[D]) {
+        let offset = (start * size_of::<D>()) as BufferAddress;
+        let size = size_of_val(data) as BufferAddress;
+
+        assert!(
+            offset.is_multiple_of(COPY_BUFFER_ALIGNMENT)
+                && size.is_multiple_of(COPY_BUFFER_ALIGNMENT),
+            "buffer writes have to be aligned to {COPY_BUFFER_ALIGNMENT} bytes, got offset {offset} and size {size}"
+        );
+
+        queue.write_buffer(&self.buffer, offset, cast_slice(data));
+    }
+
+    /// Replaces the buffer with a new one of `size` bytes and the same usage.
+    ///
+    /// The first `keep` bytes are copied over, which requires [`BufferUsages::COPY_SRC`].
+    pub(super) fn reallocate_raw(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        size: BufferAddress,
+        keep: BufferAddress,
+    ) {
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: Some(self.label()),
+            size,
+            usage: self.buffer.usage(),
+            mapped_at_creation: false,
+        });
+
+        let keep = keep.next_multiple_of(COPY_BUFFER_ALIGNMENT).min(size);
+
+        if keep > 0 {
+            let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("GpuBuffer::reallocate"),
+            });
+
+            encoder.copy_buffer_to_buffer(&self.buffer, 0, &buffer, 0, keep);
+
+            queue.submit([encoder.finish()]);
+        }
+
+        self.memory = MemoryAllocation::buffer::<B>(self.label.as_deref(), buffer.size());
+        self.buffer = buffer;
+    }
+}
+
+/// The number of elements in a growable buffer and how many fit into it.
+pub struct ElementCount {
+    pub(super) count: u32,
+    pub(super) capacity: u32,
+}
+
+impl ElementCount {
+    /// Empty buffers can't be sliced, so growable buffers keep space for at least one element.
+    pub(super) const MIN_CAPACITY: u32 = 1;
+
+    pub(super) fn new(count: u32) -> Self {
+        Self {
+            count,
+            capacity: count,
+        }
+    }
+
+    pub(super) fn empty(capacity: u32) -> Self {
+        Self { count: 0, capacity }
+    }
+
+    /// The capacity to grow to, so that `required` elements fit.
+    ///
+    /// The capacity at least doubles, so appending elements one by one is amortised O(1).
+    pub(super) fn grown_capacity(&self, required: u32) -> u32 {
+        required.max(self.capacity.saturating_mul(2))
+    }
+
+    /// The smallest capacity that still fits all elements.
+    pub(super) fn shrunk_capacity(&self) -> u32 {
+        self.count.max(Self::MIN_CAPACITY)
+    }
 }
 
 impl<T, B: BufferType> GpuBuffer<T, B> {
-    pub fn binding(&self) -> BufferBinding {
+    /// The debug label of the buffer, defaults to the type name of `T`.
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(type_name::<T>())
+    }
+
+    pub fn binding(&self) -> BufferBinding<'_> {
         self.buffer.as_entire_buffer_binding()
     }
 
-    pub fn slice(&self) -> BufferSlice {
+    pub fn slice(&self) -> BufferSlice<'_> {
         self.buffer.slice(..)
     }
+
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
 }
 
 pub trait BufferType {
     type Extra;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ElementCount;
+
+    #[test]
+    fn grown_capacity_at_least_doubles() {
+        let count = ElementCount::new(4);
+
+        assert_eq!(count.grown_capacity(5), 8);
+        assert_eq!(count.grown_capacity(8), 8);
+        assert_eq!(count.grown_capacity(20), 20);
+    }
+
+    #[test]
+    fn grown_capacity_saturates() {
+        let count = ElementCount::new(u32::MAX / 2 + 1);
+
+        assert_eq!(count.grown_capacity(u32::MAX / 2 + 2), u32::MAX);
+    }
+
+    #[test]
+    fn grown_capacity_of_empty_buffer() {
+        let count = ElementCount::empty(ElementCount::MIN_CAPACITY);
+
+        assert_eq!(count.grown_capacity(2), 2);
+        assert_eq!(count.grown_capacity(3), 3);
+    }
+
+    #[test]
+    fn shrunk_capacity_fits_count() {
+        let count = ElementCount {
+            count: 3,
+            capacity: 16,
+        };
+
+        assert_eq!(count.shrunk_capacity(), 3);
+    }
+
+    #[test]
+    fn shrunk_capacity_keeps_one_element() {
+        assert_eq!(ElementCount::empty(16).shrunk_capacity(), 1);
+    }
+}
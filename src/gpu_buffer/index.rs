@@ -1,12 +1,13 @@
 use std::fmt::Debug;
 
-use wgpu::{BufferUsages, Device, IndexFormat};
+use bytemuck::Pod;
+use wgpu::{BufferUsages, Device, IndexFormat, Queue};
 
-use super::{BufferType, GpuBuffer};
+use super::{BufferType, ElementCount, GpuBuffer};
 
 pub struct IndexBuf<const PRIMITIVE_SIZE: usize>;
 impl<const C: usize> BufferType for IndexBuf<C> {
-    type Extra = (u32, wgpu::IndexFormat);
+    type Extra = (ElementCount, wgpu::IndexFormat);
 }
 
 impl<T: TryInto<u16> + Into<u32> + Copy, const C: usize> GpuBuffer<T, IndexBuf<C>>
@@ -16,47 +17,233 @@ where
     /// Can be used with `u16` and `u32` indices.
     ///
     /// If the all indices can be stored as a `u16`, `indices` gets automaticly converted and the indices are stored as `u16`. If not, they are stored as `u32`
-    pub fn indices(device: &Device, indices: Vec<[T; C]>) -> Self {
-        let needs_u32 = indices
-            .iter()
-            .flatten()
-            .any(|i| TryInto::<u16>::try_into(*i).is_err());
-
-        if needs_u32 {
-            let indices = indices
-                .into_iter()
-                .flatten()
-                .map(Into::<u32>::into)
-                .collect::<Vec<_>>();
-            Self::new_raw(
+    ///
+    /// The debug label defaults to the type name of `T`.
+    pub fn indices(
+        device: &Device,
+        indices: impl IntoIterator<Item = [T; C]>,
+        label: Option<&str>,
+    ) -> Self {
+        let indices: Vec<[T; C]> = indices.into_iter().collect();
+
+        match Self::to_u16(&indices) {
+            Some(indices) => Self::new_raw(
                 device,
                 &indices,
-                BufferUsages::INDEX,
-                (indices.len() as u32, IndexFormat::Uint32),
-            )
-        } else {
-            let indices = indices
-                .into_iter()
-                .flatten()
-                .map(TryInto::<u16>::try_into)
-                .map(Result::unwrap)
-                .collect::<Vec<_>>();
-            Self::new_raw(
+                Self::USAGES,
+                (ElementCount::new(indices.len() as u32), IndexFormat::Uint16),
+                label,
+            ),
+            None => Self::with_format(device, indices, IndexFormat::Uint32, label),
+        }
+    }
+
+    /// Stores the indices in `format` instead of picking the smallest format.
+    ///
+    /// # Panics
+    /// If `format` is [`IndexFormat::Uint16`] and an index doesn't fit into a `u16`.
+    pub fn with_format(
+        device: &Device,
+        indices: impl IntoIterator<Item = [T; C]>,
+        format: IndexFormat,
+        label: Option<&str>,
+    ) -> Self {
+        let indices: Vec<[T; C]> = indices.into_iter().collect();
+        let count = ElementCount::new((indices.len() * C) as u32);
+
+        match format {
+            IndexFormat::Uint16 => Self::new_raw(
                 device,
-                &indices,
-                BufferUsages::INDEX,
-                (indices.len() as u32, IndexFormat::Uint16),
-            )
+                &Self::to_u16(&indices).expect("index doesn't fit into a u16"),
+                Self::USAGES,
+                (count, format),
+                label,
+            ),
+            IndexFormat::Uint32 => Self::new_raw(
+                device,
+                &Self::to_u32(indices),
+                Self::USAGES,
+                (count, format),
+                label,
+            ),
+        }
+    }
+
+    /// Replaces all indices.
+    ///
+    /// Like [`GpuBuffer::indices`], the indices are stored as `u16` if all of them fit and as `u32` if not.
+    /// The buffer gets reallocated if the index format changes or the indices don't fit into the current capacity.
+    pub fn update(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        indices: impl IntoIterator<Item = [T; C]>,
+    ) {
+        let indices: Vec<[T; C]> = indices.into_iter().collect();
+
+        match Self::to_u16(&indices) {
+            Some(indices) => self.replace(device, queue, &indices, IndexFormat::Uint16),
+            None => self.replace(device, queue, &Self::to_u32(indices), IndexFormat::Uint32),
+        }
+    }
+
+    /// Replaces all indices, storing them in `format`.
+    ///
+    /// # Panics
+    /// If `format` is [`IndexFormat::Uint16`] and an index doesn't fit into a `u16`.
+    pub fn update_with_format(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        indices: impl IntoIterator<Item = [T; C]>,
+        format: IndexFormat,
+    ) {
+        let indices: Vec<[T; C]> = indices.into_iter().collect();
+
+        match format {
+            IndexFormat::Uint16 => {
+                let indices = Self::to_u16(&indices).expect("index doesn't fit into a u16");
+                self.replace(device, queue, &indices, format);
+            }
+            IndexFormat::Uint32 => self.replace(device, queue, &Self::to_u32(indices), format),
         }
     }
+
+    /// Overwrites the primitives starting at the primitive `first_primitive`, keeping the current index format.
+    ///
+    /// # Panics
+    /// - If the written range exceeds the current count.
+    /// - If the indices are stored as `u16` and an index doesn't fit into a `u16`. Use [`GpuBuffer::update`] to switch to `u32` indices.
+    /// - If the indices are stored as `u16` and the written range is not aligned to 4 bytes.
+    pub fn update_range(
+        &self,
+        queue: &Queue,
+        first_primitive: u32,
+        indices: impl IntoIterator<Item = [T; C]>,
+    ) {
+        let indices: Vec<[T; C]> = indices.into_iter().collect();
+        let start = first_primitive as usize * C;
+
+        assert!(
+            start + indices.len() * C <= self.extra.0.count as usize,
+            "update_range out of bounds: {}..{} but count is {}",
+            start,
+            start + indices.len() * C,
+            self.extra.0.count
+        );
+
+        match self.extra.1 {
+            IndexFormat::Uint16 => {
+                let indices = Self::to_u16(&indices)
+                    .expect("index doesn't fit into the u16 index buffer, use update instead");
+                self.write_range_raw(queue, start, &indices);
+            }
+            IndexFormat::Uint32 => {
+                self.write_range_raw(queue, start, &Self::to_u32(indices));
+            }
+        }
+    }
+
+    /// `indices` as `u16` if all of them fit.
+    fn to_u16(indices: &[[T; C]]) -> Option<Vec<u16>> {
+        indices
+            .iter()
+            .flatten()
+            .map(|i| TryInto::<u16>::try_into(*i).ok())
+            .collect()
+    }
+
+    fn to_u32(indices: Vec<[T; C]>) -> Vec<u32> {
+        indices
+            .into_iter()
+            .flatten()
+            .map(Into::<u32>::into)
+            .collect()
+    }
+}
+
+impl<const C: usize> GpuBuffer<u32, IndexBuf<C>> {
+    /// Stores `indices` as `u32`, even if they would fit into `u16`.
+    pub(crate) fn indices_u32(device: &Device, indices: &[u32], label: Option<&str>) -> Self {
+        Self::new_raw(
+            device,
+            indices,
+            Self::USAGES,
+            (ElementCount::new(indices.len() as u32), IndexFormat::Uint32),
+            label,
+        )
+    }
+
+    /// Replaces all indices, storing them as `u32` even if they would fit into `u16`.
+    pub(crate) fn update_u32(&mut self, device: &Device, queue: &Queue, indices: &[u32]) {
+        self.replace(device, queue, indices, IndexFormat::Uint32);
+    }
+
+    /// Sets the number of `u32` indices, keeping the current ones. The content of added indices is unspecified.
+    pub(crate) fn resize_u32(&mut self, device: &Device, queue: &Queue, count: u32) {
+        debug_assert_eq!(self.extra.1, IndexFormat::Uint32);
+
+        if count > self.extra.0.capacity {
+            let capacity = self.extra.0.grown_capacity(count);
+
+            self.reallocate_raw(
+                device,
+                queue,
+                Self::byte_size::<u32>(capacity as usize),
+                Self::byte_size::<u32>(self.extra.0.count as usize),
+            );
+            self.extra.0.capacity = capacity;
+        }
+
+        self.extra.0.count = count;
+    }
+
+    /// Overwrites the `u32` indices starting at `start`.
+    pub(crate) fn update_range_u32(&self, queue: &Queue, start: u32, indices: &[u32]) {
+        debug_assert!(start as usize + indices.len() <= self.extra.0.count as usize);
+
+        self.write_range_raw(queue, start as usize, indices);
+    }
 }
 
 impl<T, const C: usize> GpuBuffer<T, IndexBuf<C>> {
+    const USAGES: BufferUsages = BufferUsages::INDEX
+        .union(BufferUsages::COPY_DST)
+        .union(BufferUsages::COPY_SRC);
+
     pub fn count(&self) -> u32 {
-        self.extra.0
+        self.extra.0.count
+    }
+
+    /// The number of indices that fit into the buffer without reallocating it.
+    pub fn capacity(&self) -> u32 {
+        self.extra.0.capacity
     }
 
     pub fn index_format(&self) -> IndexFormat {
         self.extra.1
     }
+
+    fn replace<D: Pod>(&mut self, device: &Device, queue: &Queue, raw: &[D], format: IndexFormat) {
+        let count = raw.len() as u32;
+
+        if format != self.extra.1 || count > self.extra.0.capacity {
+            let capacity = if count > self.extra.0.capacity {
+                self.extra.0.grown_capacity(count)
+            } else {
+                self.extra.0.capacity
+            };
+
+            *self = Self::new_uninit::<D>(
+                device,
+                capacity as usize,
+                Self::USAGES,
+                (ElementCount::empty(capacity), format),
+                self.label.as_deref(),
+            );
+        }
+
+        self.write_raw(queue, 0, raw);
+        self.extra.0.count = count;
+    }
 }
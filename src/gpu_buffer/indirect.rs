@@ -0,0 +1,61 @@
+use wgpu::{BufferAddress, BufferUsages, Device, Queue, util::DrawIndexedIndirectArgs};
+
+use super::{BufferType, GpuBuffer};
+
+pub struct IndirectBuf;
+
+impl BufferType for IndirectBuf {
+    type Extra = u32;
+}
+
+impl GpuBuffer<DrawIndexedIndirectArgs, IndirectBuf> {
+    /// Can be bound as a storage buffer, so a compute shader can write the draw arguments.
+    const USAGES: BufferUsages = BufferUsages::INDIRECT
+        .union(BufferUsages::STORAGE)
+        .union(BufferUsages::COPY_DST)
+        .union(BufferUsages::COPY_SRC);
+
+    pub fn indirect(
+        device: &Device,
+        args: impl IntoIterator<Item = DrawIndexedIndirectArgs>,
+        label: Option<&str>,
+    ) -> Self {
+        let args: Vec<_> = args.into_iter().collect();
+
+        Self::new_raw(device, &args, Self::USAGES, args.len() as u32, label)
+    }
+
+    /// Creates `count` zeroed draw commands, e.g. to be filled by a compute shader.
+    pub fn zeroed(device: &Device, count: u32, label: Option<&str>) -> Self {
+        Self::indirect(
+            device,
+            vec![DrawIndexedIndirectArgs::default(); count as usize],
+            label,
+        )
+    }
+
+    /// Overwrites the draw commands starting at `start`.
+    ///
+    /// # Panics
+    /// If the written range exceeds the current count.
+    pub fn update(&self, queue: &Queue, start: u32, args: &[DrawIndexedIndirectArgs]) {
+        assert!(
+            start as usize + args.len() <= self.extra as usize,
+            "update out of bounds: {}..{} but count is {}",
+            start,
+            start as usize + args.len(),
+            self.extra
+        );
+
+        self.write_range_raw(queue, start as usize, args);
+    }
+
+    pub fn count(&self) -> u32 {
+        self.extra
+    }
+
+    /// The byte offset of the draw command at `index`.
+    pub fn offset(&self, index: u32) -> BufferAddress {
+        (index as usize * size_of::<DrawIndexedIndirectArgs>()) as BufferAddress
+    }
+}
@@ -1,48 +1,89 @@
-use bytemuck::cast_slice;
 use wgpu::{BufferUsages, Device, Queue};
 
 use crate::mesh::MeshInstance;
 
-use super::{BufferType, GpuBuffer};
+use super::{BufferType, ElementCount, GpuBuffer};
 
 pub struct InstanceBuf;
 
 impl BufferType for InstanceBuf {
-    type Extra = InstanceCount;
-}
-
-pub struct InstanceCount {
-    count: u32,
-    capacity: u32,
-}
-
-impl InstanceCount {
-    fn new(count: u32) -> Self {
-        Self {
-            count,
-            capacity: count,
-        }
-    }
+    type Extra = ElementCount;
 }
 
 impl<T> GpuBuffer<T, InstanceBuf> {
+    const USAGES: BufferUsages = BufferUsages::VERTEX
+        .union(BufferUsages::COPY_DST)
+        .union(BufferUsages::COPY_SRC);
+
     pub fn count(&self) -> u32 {
         self.extra.count
     }
+
+    /// The number of instances that fit into the buffer without reallocating it.
+    pub fn capacity(&self) -> u32 {
+        self.extra.capacity
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.extra.count == 0
+    }
+
+    /// Shortens the buffer to `count` instances. Has no effect if `count` is greater than the current count.
+    ///
+    /// The capacity stays the same.
+    pub fn truncate(&mut self, count: u32) {
+        self.extra.count = self.extra.count.min(count);
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
 }
 
 impl<T: MeshInstance> GpuBuffer<T, InstanceBuf> {
-    pub fn instances(device: &Device, instances: impl IntoIterator<Item = T>) -> Self {
-        let raw: Vec<T::Raw> = instances.into_iter().map(Into::into).collect();
+    /// The debug label defaults to the type name of `T`.
+    ///
+    /// Empty buffers can't be sliced, so space for at least one instance is allocated.
+    pub fn instances(
+        device: &Device,
+        instances: impl IntoIterator<Item = T>,
+        label: Option<&str>,
+    ) -> Self {
+        let mut raw: Vec<T::Raw> = instances.into_iter().map(Into::into).collect();
+        let count = raw.len() as u32;
+
+        if raw.is_empty() {
+            raw.push(bytemuck::Zeroable::zeroed());
+        }
 
         Self::new_raw(
             device,
             &raw,
-            BufferUsages::VERTEX | BufferUsages::COPY_DST,
-            InstanceCount::new(raw.len() as u32),
+            Self::USAGES,
+            ElementCount {
+                count,
+                capacity: raw.len() as u32,
+            },
+            label,
+        )
+    }
+
+    /// Creates an empty buffer with space for `capacity` instances, but at least one.
+    pub fn with_capacity(device: &Device, capacity: u32, label: Option<&str>) -> Self {
+        let capacity = capacity.max(ElementCount::MIN_CAPACITY);
+
+        Self::new_uninit::<T::Raw>(
+            device,
+            capacity as usize,
+            Self::USAGES,
+            ElementCount::empty(capacity),
+            label,
         )
     }
 
+    /// Replaces all instances.
+    ///
+    /// The buffer only gets reallocated if the instances don't fit into the current capacity.
     pub fn update(
         &mut self,
         device: &Device,
@@ -50,17 +91,84 @@ impl<T: MeshInstance> GpuBuffer<T, InstanceBuf> {
         instances: impl IntoIterator<Item = T>,
     ) {
         let raw: Vec<T::Raw> = instances.into_iter().map(Into::into).collect();
+        let count = raw.len() as u32;
+
+        if count > self.extra.capacity {
+            let capacity = self.extra.grown_capacity(count);
+
+            *self = Self::with_capacity(device, capacity, self.label.as_deref());
+        }
+
+        self.write_raw(queue, 0, &raw);
+        self.extra.count = count;
+    }
+
+    /// Overwrites the instances starting at `start`.
+    ///
+    /// # Panics
+    /// If the written range exceeds the current count.
+    pub fn update_range(&self, queue: &Queue, start: u32, instances: impl IntoIterator<Item = T>) {
+        let raw: Vec<T::Raw> = instances.into_iter().map(Into::into).collect();
+
+        assert!(
+            start as usize + raw.len() <= self.extra.count as usize,
+            "update_range out of bounds: {}..{} but count is {}",
+            start,
+            start as usize + raw.len(),
+            self.extra.count
+        );
+
+        self.write_range_raw(queue, start as usize, &raw);
+    }
+
+    pub fn push(&mut self, device: &Device, queue: &Queue, instance: T) {
+        self.extend(device, queue, [instance]);
+    }
+
+    /// Appends `instances`, growing the buffer if needed.
+    pub fn extend(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        instances: impl IntoIterator<Item = T>,
+    ) {
+        let raw: Vec<T::Raw> = instances.into_iter().map(Into::into).collect();
+        let count = self.extra.count + raw.len() as u32;
+
+        self.reserve(device, queue, raw.len() as u32);
+
+        self.write_range_raw(queue, self.extra.count as usize, &raw);
+        self.extra.count = count;
+    }
+
+    /// Makes sure that at least `additional` more instances fit into the buffer.
+    pub fn reserve(&mut self, device: &Device, queue: &Queue, additional: u32) {
+        let required = self.extra.count + additional;
+
+        if required > self.extra.capacity {
+            self.reallocate(device, queue, self.extra.grown_capacity(required));
+        }
+    }
 
-        if raw.len() > self.extra.capacity as usize {
-            queue.write_buffer(&self.buffer, 0, cast_slice(&raw));
-            self.extra.count = raw.len() as u32;
-        } else {
-            *self = Self::new_raw(
-                device,
-                &raw,
-                BufferUsages::VERTEX | BufferUsages::COPY_DST,
-                InstanceCount::new(raw.len() as u32),
-            );
+    /// Reallocates the buffer so that the capacity matches the count, empty buffers keep space for one instance.
+    pub fn shrink_to_fit(&mut self, device: &Device, queue: &Queue) {
+        let capacity = self.extra.shrunk_capacity();
+
+        if self.extra.capacity > capacity {
+            self.reallocate(device, queue, capacity);
         }
     }
+
+    /// Moves the current instances into a new buffer with space for `capacity` instances.
+    fn reallocate(&mut self, device: &Device, queue: &Queue, capacity: u32) {
+        self.reallocate_raw(
+            device,
+            queue,
+            Self::byte_size::<T::Raw>(capacity as usize),
+            Self::byte_size::<T::Raw>(self.extra.count as usize),
+        );
+
+        self.extra.capacity = capacity;
+        self.extra.count = self.extra.count.min(capacity);
+    }
 }
@@ -1,19 +1,33 @@
 mod gpu_buffer;
 mod index;
+mod indirect;
 mod instance;
+mod read;
+mod storage;
 mod uniform;
+mod uniform_arena;
+mod uniform_array;
 mod vertex;
 
 pub use gpu_buffer::BufferType;
+pub use gpu_buffer::ElementCount;
 pub use gpu_buffer::GpuBuffer;
 
 pub use index::IndexBuf;
+pub use indirect::IndirectBuf;
 pub use instance::InstanceBuf;
+pub use read::{ReadBuffer, ReadError};
+pub use storage::StorageBuf;
 pub use uniform::UniformBuf;
 pub use uniform::UniformExt;
+pub use uniform_arena::{UniformArena, UniformSlice};
+pub use uniform_array::UniformArrayBuf;
 pub use vertex::VertexBuf;
 
 pub type UniformBuffer<T> = GpuBuffer<T, UniformBuf>;
+pub type UniformArrayBuffer<T> = GpuBuffer<T, UniformArrayBuf>;
 pub type VertexBuffer<T> = GpuBuffer<T, VertexBuf>;
 pub type IndexBuffer<T, const PRIMITIVE_SIZE: usize> = GpuBuffer<T, IndexBuf<PRIMITIVE_SIZE>>;
 pub type InstanceBuffer<T> = GpuBuffer<T, InstanceBuf>;
+pub type StorageBuffer<T> = GpuBuffer<T, StorageBuf>;
+pub type IndirectBuffer = GpuBuffer<wgpu::util::DrawIndexedIndirectArgs, IndirectBuf>;
@@ -0,0 +1,248 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    pin::{Pin, pin},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use bytemuck::{Pod, pod_collect_to_vec};
+use wgpu::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Device, IndexFormat,
+    MapMode, PollError, PollType, Queue, util::DrawIndexedIndirectArgs,
+};
+
+use crate::{
+    Uniform,
+    mesh::{MeshInstance, Vertex},
+};
+
+use super::{
+    BufferType, GpuBuffer, IndexBuf, IndirectBuf, InstanceBuf, StorageBuf, UniformBuf, VertexBuf,
+};
+
+/// Buffer types that can be read back with [`GpuBuffer::read`].
+pub trait ReadBuffer<T>: BufferType {
+    /// The type that is stored on the GPU.
+    type Raw: Pod;
+
+    /// The number of valid elements in the buffer.
+    fn len(extra: &Self::Extra) -> usize;
+}
+
+impl<T: Vertex> ReadBuffer<T> for VertexBuf {
+    type Raw = T::Raw;
+
+    fn len(extra: &Self::Extra) -> usize {
+        extra.count as usize
+    }
+}
+
+impl<T: MeshInstance> ReadBuffer<T> for InstanceBuf {
+    type Raw = T::Raw;
+
+    fn len(extra: &Self::Extra) -> usize {
+        extra.count as usize
+    }
+}
+
+impl<T: Uniform> ReadBuffer<T> for UniformBuf {
+    type Raw = T::Raw;
+
+    fn len(_: &Self::Extra) -> usize {
+        1
+    }
+}
+
+impl<T: Uniform> ReadBuffer<T> for StorageBuf {
+    type Raw = T::Raw;
+
+    fn len(extra: &Self::Extra) -> usize {
+        extra.count as usize
+    }
+}
+
+impl ReadBuffer<DrawIndexedIndirectArgs> for IndirectBuf {
+    type Raw = DrawIndexedIndirectArgs;
+
+    fn len(extra: &Self::Extra) -> usize {
+        *extra as usize
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    /// The staging buffer could not be mapped
+    Map(BufferAsyncError),
+    /// Waiting for the copy to finish failed
+    Poll(PollError),
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Map(err) => write!(f, "failed to map the staging buffer: {}", err),
+            ReadError::Poll(err) => write!(f, "failed to wait for the buffer copy: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+impl<T, B: ReadBuffer<T>> GpuBuffer<T, B> {
+    /// Copies the contents of the buffer into a staging buffer and reads it back.
+    ///
+    /// The copy is submitted immediately. On native backends the returned future only completes once the device is polled,
+    /// either with [`Device::poll`] or by using [`GpuBuffer::read_blocking`].
+    pub fn read(
+        &self,
+        device: &Device,
+        queue: &Queue,
+    ) -> impl Future<Output = Result<Vec<B::Raw>, ReadError>> + use<T, B> {
+        self.read_raw(device, queue, B::len(&self.extra))
+    }
+
+    /// Like [`GpuBuffer::read`], but blocks until the data is available.
+    pub fn read_blocking(&self, device: &Device, queue: &Queue) -> Result<Vec<B::Raw>, ReadError> {
+        block_on(device, self.read(device, queue))
+    }
+}
+
+impl<T, const C: usize> GpuBuffer<T, IndexBuf<C>> {
+    /// Reads back the indices, `u16` indices are converted to `u32`.
+    ///
+    /// See [`GpuBuffer::read`] for when the future completes.
+    pub fn read_indices(
+        &self,
+        device: &Device,
+        queue: &Queue,
+    ) -> impl Future<Output = Result<Vec<u32>, ReadError>> + use<T, C> {
+        let (count, format) = (self.extra.0.count as usize, self.extra.1);
+        let read = self.read_raw::<u8>(device, queue, count * format.byte_size());
+
+        async move {
+            let bytes = read.await?;
+
+            Ok(match format {
+                IndexFormat::Uint16 => pod_collect_to_vec::<u8, u16>(&bytes)
+                    .into_iter()
+                    .map(u32::from)
+                    .collect(),
+                IndexFormat::Uint32 => pod_collect_to_vec(&bytes),
+            })
+        }
+    }
+
+    /// Like [`GpuBuffer::read_indices`], but blocks until the data is available.
+    pub fn read_indices_blocking(
+        &self,
+        device: &Device,
+        queue: &Queue,
+    ) -> Result<Vec<u32>, ReadError> {
+        block_on(device, self.read_indices(device, queue))
+    }
+}
+
+impl<T, B: BufferType> GpuBuffer<T, B> {
+    /// Reads back the first `len` elements of the buffer as `D`.
+    fn read_raw<D: Pod>(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        len: usize,
+    ) -> impl Future<Output = Result<Vec<D>, ReadError>> + use<T, B, D> {
+        let size = Self::byte_size::<D>(len);
+
+        let staging = device.create_buffer(&BufferDescriptor {
+            label: Some("GpuBuffer::read staging"),
+            size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("GpuBuffer::read"),
+        });
+
+        encoder.copy_buffer_to_buffer(&self.buffer, 0, &staging, 0, size);
+        queue.submit([encoder.finish()]);
+
+        let mapped = MapFuture::default();
+        let state = mapped.state.clone();
+
+        // Empty buffers can't be mapped
+        if size > 0 {
+            staging.map_async(MapMode::Read, .., move |result| {
+                let mut state = state.lock().unwrap();
+                state.result = Some(result);
+
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+        }
+
+        async move {
+            if size == 0 {
+                return Ok(Vec::new());
+            }
+
+            mapped.await.map_err(ReadError::Map)?;
+
+            let data = {
+                let range = staging.get_mapped_range(..);
+                // The mapped range is not guaranteed to be aligned for `D`
+                pod_collect_to_vec(&range[..len * size_of::<D>()])
+            };
+
+            staging.unmap();
+
+            Ok(data)
+        }
+    }
+}
+
+/// Polls the device until `future` completes.
+fn block_on<R>(
+    device: &Device,
+    future: impl Future<Output = Result<R, ReadError>>,
+) -> Result<R, ReadError> {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+
+    loop {
+        device.poll(PollType::Wait).map_err(ReadError::Poll)?;
+
+        if let Poll::Ready(result) = future.as_mut().poll(&mut context) {
+            return result;
+        }
+    }
+}
+
+#[derive(Default)]
+struct MapState {
+    result: Option<Result<(), BufferAsyncError>>,
+    waker: Option<Waker>,
+}
+
+/// Completes when the `map_async` callback sharing `state` was called.
+#[derive(Default)]
+struct MapFuture {
+    state: Arc<Mutex<MapState>>,
+}
+
+impl Future for MapFuture {
+    type Output = Result<(), BufferAsyncError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use wgpu::{BufferUsages, Device, Queue};
+
+use crate::Uniform;
+
+use super::{BufferType, ElementCount, GpuBuffer};
+
+pub struct StorageBuf;
+
+impl BufferType for StorageBuf {
+    type Extra = ElementCount;
+}
+
+impl<T> GpuBuffer<T, StorageBuf> {
+    const USAGES: BufferUsages = BufferUsages::STORAGE
+        .union(BufferUsages::COPY_DST)
+        .union(BufferUsages::COPY_SRC);
+
+    pub fn count(&self) -> u32 {
+        self.extra.count
+    }
+
+    /// The number of elements that fit into the buffer without reallocating it.
+    pub fn capacity(&self) -> u32 {
+        self.extra.capacity
+    }
+}
+
+impl<T: Uniform> GpuBuffer<T, StorageBuf> {
+    /// A read-only storage buffer, bound as `array<T>`.
+    ///
+    /// Empty buffers can't be bound, so space for at least one element is allocated.
+    /// The debug label defaults to the type name of `T`.
+    pub fn storage(
+        device: &Device,
+        values: impl IntoIterator<Item = T>,
+        label: Option<&str>,
+    ) -> Self {
+        let mut raw: Vec<T::Raw> = values.into_iter().map(Into::into).collect();
+        let count = raw.len() as u32;
+
+        if raw.is_empty() {
+            raw.push(bytemuck::Zeroable::zeroed());
+        }
+
+        Self::new_raw(
+            device,
+            &raw,
+            Self::USAGES,
+            ElementCount {
+                count,
+                capacity: raw.len() as u32,
+            },
+            label,
+        )
+    }
+
+    /// Replaces all elements.
+    ///
+    /// The buffer only gets reallocated if the elements don't fit into the current capacity, which changes its binding.
+    pub fn update(&mut self, device: &Device, queue: &Queue, values: impl IntoIterator<Item = T>) {
+        let raw: Vec<T::Raw> = values.into_iter().map(Into::into).collect();
+        let count = raw.len() as u32;
+
+        if count > self.extra.capacity {
+            let capacity = self.extra.grown_capacity(count);
+
+            *self = Self::new_uninit::<T::Raw>(
+                device,
+                capacity as usize,
+                Self::USAGES,
+                ElementCount::empty(capacity),
+                self.label.as_deref(),
+            );
+        }
+
+        self.write_raw(queue, 0, &raw);
+        self.extra.count = count;
+    }
+}
@@ -12,14 +12,16 @@ impl BufferType for UniformBuf {
 }
 
 impl<T: Uniform> GpuBuffer<T, UniformBuf> {
-    pub fn uniform(device: &Device, value: T) -> Self {
+    /// The debug label defaults to the type name of `T`.
+    pub fn uniform(device: &Device, value: T, label: Option<&str>) -> Self {
         let raw: T::Raw = value.into();
 
         Self::new_raw(
             device,
             &[raw],
-            BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            BufferUsages::UNIFORM | BufferUsages::COPY_DST | BufferUsages::COPY_SRC,
             (),
+            label,
         )
     }
 
@@ -36,6 +38,6 @@ pub trait UniformExt: Sized {
 
 impl<T: Uniform> UniformExt for T {
     fn buffer(self, device: &Device) -> GpuBuffer<Self, UniformBuf> {
-        GpuBuffer::uniform(device, self)
+        GpuBuffer::uniform(device, self, None)
     }
 }
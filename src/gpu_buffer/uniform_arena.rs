@@ -0,0 +1,181 @@
+use std::{
+    collections::VecDeque,
+    marker::PhantomData,
+    num::NonZero,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use bytemuck::cast_slice;
+use wgpu::{Buffer, BufferAddress, BufferBinding, BufferDescriptor, BufferUsages, Device, Queue};
+
+use crate::Uniform;
+
+/// A ring buffer for uniforms that only live for a single frame.
+///
+/// Every [`UniformArena::push`] writes the value at the next free offset instead of creating a new buffer.
+/// Call [`UniformArena::end_frame`] after submitting the frame, its memory gets reused once the GPU has finished the submission.
+/// If the ring is full, a buffer with twice the size is created. Slices of earlier frames keep the old buffer alive.
+pub struct UniformArena {
+    buffer: Buffer,
+    capacity: BufferAddress,
+    alignment: BufferAddress,
+    /// Positions grow monotonically, the offset in the buffer is `position % capacity`
+    head: BufferAddress,
+    frame_start: BufferAddress,
+    in_flight: VecDeque<FrameRegion>,
+}
+
+/// The memory used by a submitted frame.
+struct FrameRegion {
+    start: BufferAddress,
+    done: Arc<AtomicBool>,
+}
+
+impl UniformArena {
+    const USAGES: BufferUsages = BufferUsages::UNIFORM.union(BufferUsages::COPY_DST);
+
+    /// `capacity` is the initial size in bytes, rounded up to `min_uniform_buffer_offset_alignment`.
+    pub fn new(device: &Device, capacity: BufferAddress) -> Self {
+        let alignment = device.limits().min_uniform_buffer_offset_alignment as BufferAddress;
+        let capacity = capacity.max(1).next_multiple_of(alignment);
+
+        Self {
+            buffer: Self::create_buffer(device, capacity),
+            capacity,
+            alignment,
+            head: 0,
+            frame_start: 0,
+            in_flight: VecDeque::new(),
+        }
+    }
+
+    /// Writes `value` into the arena. The returned slice can be bound like a [`UniformBuffer`](crate::UniformBuffer).
+    pub fn push<T: Uniform>(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        value: T,
+    ) -> UniformSlice<T> {
+        let raw: T::Raw = value.into();
+        let size = size_of::<T::Raw>() as BufferAddress;
+
+        self.reclaim();
+
+        let position = match self.allocate(size) {
+            Some(position) => position,
+            None => {
+                self.grow(device, size);
+                self.allocate(size)
+                    .expect("a grown arena always fits the value")
+            }
+        };
+
+        let offset = position % self.capacity;
+        queue.write_buffer(&self.buffer, offset, cast_slice(&[raw]));
+
+        UniformSlice {
+            buffer: self.buffer.clone(),
+            offset,
+            size: NonZero::new(size).expect("uniforms can't be zero sized"),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Marks the end of a frame. Has to be called after the frame's command buffers were submitted to `queue`.
+    pub fn end_frame(&mut self, queue: &Queue) {
+        let done = Arc::new(AtomicBool::new(false));
+
+        let callback_done = done.clone();
+        queue.on_submitted_work_done(move || callback_done.store(true, Ordering::Release));
+
+        self.in_flight.push_back(FrameRegion {
+            start: self.frame_start,
+            done,
+        });
+        self.frame_start = self.head;
+    }
+
+    /// The size of the ring buffer in bytes.
+    pub fn capacity(&self) -> BufferAddress {
+        self.capacity
+    }
+
+    /// Frees the memory of all frames the GPU has finished.
+    fn reclaim(&mut self) {
+        while self
+            .in_flight
+            .front()
+            .is_some_and(|frame| frame.done.load(Ordering::Acquire))
+        {
+            self.in_flight.pop_front();
+        }
+    }
+
+    /// Returns the position of `size` free bytes, skipping the end of the buffer if they don't fit there.
+    fn allocate(&mut self, size: BufferAddress) -> Option<BufferAddress> {
+        let tail = self
+            .in_flight
+            .front()
+            .map_or(self.frame_start, |frame| frame.start);
+
+        let mut position = self.head.next_multiple_of(self.alignment);
+
+        if position % self.capacity + size > self.capacity {
+            position = position.next_multiple_of(self.capacity);
+        }
+
+        let end = position + size;
+
+        if end - tail > self.capacity {
+            return None;
+        }
+
+        self.head = end;
+        Some(position)
+    }
+
+    /// Replaces the buffer with a larger one. Frames in flight keep using the old buffer.
+    fn grow(&mut self, device: &Device, required: BufferAddress) {
+        self.capacity = (self.capacity * 2).max(required.next_multiple_of(self.alignment));
+        self.buffer = Self::create_buffer(device, self.capacity);
+
+        self.head = 0;
+        self.frame_start = 0;
+        self.in_flight.clear();
+    }
+
+    fn create_buffer(device: &Device, capacity: BufferAddress) -> Buffer {
+        device.create_buffer(&BufferDescriptor {
+            label: Some("UniformArena"),
+            size: capacity,
+            usage: Self::USAGES,
+            mapped_at_creation: false,
+        })
+    }
+}
+
+/// A uniform value inside of a [`UniformArena`].
+pub struct UniformSlice<T> {
+    buffer: Buffer,
+    offset: BufferAddress,
+    size: NonZero<BufferAddress>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> UniformSlice<T> {
+    pub fn binding(&self) -> BufferBinding<'_> {
+        BufferBinding {
+            buffer: &self.buffer,
+            offset: self.offset,
+            size: Some(self.size),
+        }
+    }
+
+    /// The offset of the value in the arena's buffer.
+    pub fn offset(&self) -> BufferAddress {
+        self.offset
+    }
+}
@@ -0,0 +1,106 @@
+use bytemuck::bytes_of;
+use wgpu::{BufferAddress, BufferBinding, BufferSize, BufferUsages, Device, DynamicOffset, Queue};
+
+use crate::Uniform;
+
+use super::{BufferType, GpuBuffer};
+
+pub struct UniformArrayBuf;
+
+impl BufferType for UniformArrayBuf {
+    type Extra = UniformArrayLayout;
+}
+
+pub struct UniformArrayLayout {
+    count: u32,
+    /// The distance between two elements in bytes, a multiple of `min_uniform_buffer_offset_alignment`
+    stride: BufferAddress,
+}
+
+impl<T: Uniform> GpuBuffer<T, UniformArrayBuf> {
+    /// Stores every value at its own offset, so a single element can be bound with a dynamic offset.
+    ///
+    /// The binding always covers one element, so space for at least one element is allocated.
+    pub fn uniform_array(
+        device: &Device,
+        values: impl IntoIterator<Item = T>,
+        label: Option<&str>,
+    ) -> Self {
+        let alignment = device.limits().min_uniform_buffer_offset_alignment as usize;
+        let stride = size_of::<T::Raw>().next_multiple_of(alignment);
+
+        let mut bytes = Vec::new();
+
+        for value in values {
+            let raw: T::Raw = value.into();
+
+            bytes.extend_from_slice(bytes_of(&raw));
+            bytes.resize(bytes.len().next_multiple_of(stride), 0);
+        }
+
+        let count = (bytes.len() / stride) as u32;
+
+        if bytes.is_empty() {
+            bytes.resize(stride, 0);
+        }
+
+        Self::new_raw(
+            device,
+            &bytes,
+            BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            UniformArrayLayout {
+                count,
+                stride: stride as BufferAddress,
+            },
+            label,
+        )
+    }
+
+    /// Overwrites the element at `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn update(&self, queue: &Queue, index: u32, value: T) {
+        let raw: T::Raw = value.into();
+
+        queue.write_buffer(
+            &self.buffer,
+            self.offset(index) as BufferAddress,
+            bytes_of(&raw),
+        );
+    }
+
+    /// Binds a single element, the element is selected with the dynamic offset.
+    pub fn element_binding(&self) -> BufferBinding<'_> {
+        BufferBinding {
+            buffer: &self.buffer,
+            offset: 0,
+            size: BufferSize::new(size_of::<T::Raw>() as BufferAddress),
+        }
+    }
+}
+
+impl<T> GpuBuffer<T, UniformArrayBuf> {
+    pub fn count(&self) -> u32 {
+        self.extra.count
+    }
+
+    pub fn stride(&self) -> BufferAddress {
+        self.extra.stride
+    }
+
+    /// The dynamic offset of the element at `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn offset(&self, index: u32) -> DynamicOffset {
+        assert!(
+            index < self.extra.count,
+            "uniform array index {} out of bounds, count is {}",
+            index,
+            self.extra.count
+        );
+
+        (index as BufferAddress * self.extra.stride) as DynamicOffset
+    }
+}
@@ -1,20 +1,108 @@
-use wgpu::{BufferUsages, Device};
+use wgpu::{BufferUsages, Device, Queue};
 
 use crate::mesh::Vertex;
 
-use super::{BufferType, GpuBuffer};
-
+use super::{BufferType, ElementCount, GpuBuffer};
 
 pub struct VertexBuf;
 
 impl BufferType for VertexBuf {
-    type Extra = u32;
+    type Extra = ElementCount;
+}
+
+impl<T> GpuBuffer<T, VertexBuf> {
+    const USAGES: BufferUsages = BufferUsages::VERTEX
+        .union(BufferUsages::COPY_DST)
+        .union(BufferUsages::COPY_SRC);
+
+    pub fn count(&self) -> u32 {
+        self.extra.count
+    }
+
+    /// The number of vertices that fit into the buffer without reallocating it.
+    pub fn capacity(&self) -> u32 {
+        self.extra.capacity
+    }
 }
 
 impl<T: Vertex> GpuBuffer<T, VertexBuf> {
-    pub fn vertices(device: &Device, vertices: impl IntoIterator<Item = T>) -> Self {
+    /// The debug label defaults to the type name of `T`.
+    pub fn vertices(
+        device: &Device,
+        vertices: impl IntoIterator<Item = T>,
+        label: Option<&str>,
+    ) -> Self {
+        let raw: Vec<T::Raw> = vertices.into_iter().map(Into::into).collect();
+
+        Self::new_raw(
+            device,
+            &raw,
+            Self::USAGES,
+            ElementCount::new(raw.len() as u32),
+            label,
+        )
+    }
+
+    /// Replaces all vertices.
+    ///
+    /// The buffer only gets reallocated if the vertices don't fit into the current capacity.
+    pub fn update(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        vertices: impl IntoIterator<Item = T>,
+    ) {
         let raw: Vec<T::Raw> = vertices.into_iter().map(Into::into).collect();
+        let count = raw.len() as u32;
+
+        if count > self.extra.capacity {
+            let capacity = self.extra.grown_capacity(count);
+
+            *self = Self::new_uninit::<T::Raw>(
+                device,
+                capacity as usize,
+                Self::USAGES,
+                ElementCount::empty(capacity),
+                self.label.as_deref(),
+            );
+        }
+
+        self.write_raw(queue, 0, &raw);
+        self.extra.count = count;
+    }
+
+    /// Overwrites the vertices starting at `start`.
+    ///
+    /// # Panics
+    /// If the written range exceeds the current count.
+    pub fn update_range(&self, queue: &Queue, start: u32, vertices: impl IntoIterator<Item = T>) {
+        let raw: Vec<T::Raw> = vertices.into_iter().map(Into::into).collect();
+
+        assert!(
+            start as usize + raw.len() <= self.extra.count as usize,
+            "update_range out of bounds: {}..{} but count is {}",
+            start,
+            start as usize + raw.len(),
+            self.extra.count
+        );
+
+        self.write_range_raw(queue, start as usize, &raw);
+    }
+
+    /// Sets the number of vertices, keeping the current ones. The content of added vertices is unspecified.
+    pub(crate) fn resize(&mut self, device: &Device, queue: &Queue, count: u32) {
+        if count > self.extra.capacity {
+            let capacity = self.extra.grown_capacity(count);
+
+            self.reallocate_raw(
+                device,
+                queue,
+                Self::byte_size::<T::Raw>(capacity as usize),
+                Self::byte_size::<T::Raw>(self.extra.count as usize),
+            );
+            self.extra.capacity = capacity;
+        }
 
-        Self::new_raw(device, &raw, BufferUsages::VERTEX, raw.len() as u32)
+        self.extra.count = count;
     }
 }
@@ -1,8 +1,19 @@
+#![allow(clippy::module_inception)]
+
+pub mod animation;
+pub mod bounds;
+mod debug_group;
+#[cfg(feature = "gltf")]
+pub mod gltf;
 mod gpu_buffer;
 mod renderer;
 pub mod sample;
 
+pub mod memory_stats;
 pub mod mesh;
+#[cfg(feature = "obj")]
+pub mod obj;
+pub mod render_graph;
 pub mod shader;
 pub mod texture;
 mod uniform;
@@ -10,12 +21,23 @@ pub mod uniform_binding;
 
 pub use uniform::Uniform;
 
+pub use debug_group::{DebugGroup, DebugGroupExt};
+
 pub use gpu_buffer::GpuBuffer;
 pub use gpu_buffer::IndexBuffer;
+pub use gpu_buffer::IndirectBuffer;
 pub use gpu_buffer::InstanceBuffer;
+pub use gpu_buffer::ReadBuffer;
+pub use gpu_buffer::ReadError;
+pub use gpu_buffer::StorageBuffer;
+pub use gpu_buffer::UniformArena;
+pub use gpu_buffer::UniformArrayBuffer;
 pub use gpu_buffer::UniformBuffer;
 pub use gpu_buffer::UniformExt;
+pub use gpu_buffer::UniformSlice;
 pub use gpu_buffer::VertexBuffer;
 
+pub use memory_stats::GpuMemoryStats;
+
 pub use bytemuck;
 pub use cgmath;
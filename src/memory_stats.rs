@@ -0,0 +1,351 @@
+use std::{collections::BTreeMap, fmt::Display, sync::Mutex};
+
+use cgmath::Vector2;
+use wgpu::{Extent3d, TextureFormat};
+
+/// Live GPU allocations, grouped by kind, category and label.
+static REGISTRY: Mutex<BTreeMap<AllocationKey, Usage>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct AllocationKey {
+    kind: AllocationKind,
+    category: String,
+    label: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AllocationKind {
+    Buffer,
+    Texture,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Usage {
+    count: usize,
+    bytes: u64,
+}
+
+/// A snapshot of the GPU memory allocated through this crate.
+///
+/// Buffers are categorized by their [`BufferType`](crate::gpu_buffer::BufferType), textures by their [`PixelFormat`](crate::texture::PixelFormat).
+/// The [`Display`] impl prints the snapshot as a table.
+#[derive(Debug, Clone)]
+pub struct GpuMemoryStats {
+    pub entries: Vec<MemoryStatsEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryStatsEntry {
+    pub kind: AllocationKind,
+    pub category: String,
+    pub label: Option<String>,
+    /// The number of live allocations
+    pub count: usize,
+    pub bytes: u64,
+}
+
+impl GpuMemoryStats {
+    /// The allocations that are currently alive.
+    pub fn snapshot() -> Self {
+        let registry = REGISTRY.lock().unwrap();
+
+        let entries = registry
+            .iter()
+            .map(|(key, usage)| MemoryStatsEntry {
+                kind: key.kind,
+                category: key.category.clone(),
+                label: key.label.clone(),
+                count: usage.count,
+                bytes: usage.bytes,
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.bytes).sum()
+    }
+
+    pub fn kind_bytes(&self, kind: AllocationKind) -> u64 {
+        self.entries
+            .iter()
+            .filter(|entry| entry.kind == kind)
+            .map(|entry| entry.bytes)
+            .sum()
+    }
+}
+
+impl Display for GpuMemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = self
+            .entries
+            .iter()
+            .map(|entry| {
+                [
+                    format!("{:?}", entry.kind),
+                    entry.category.clone(),
+                    entry.label.clone().unwrap_or_default(),
+                    entry.count.to_string(),
+                    format_bytes(entry.bytes),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let header = ["kind", "category", "label", "count", "size"].map(String::from);
+        let total = [
+            "total".to_string(),
+            String::new(),
+            String::new(),
+            self.entries
+                .iter()
+                .map(|entry| entry.count)
+                .sum::<usize>()
+                .to_string(),
+            format_bytes(self.total_bytes()),
+        ];
+
+        let mut widths = [0; 5];
+
+        for row in std::iter::once(&header).chain(&rows).chain([&total]) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        let write_row = |f: &mut std::fmt::Formatter<'_>, row: &[String; 5]| {
+            writeln!(
+                f,
+                "{:<w0$}  {:<w1$}  {:<w2$}  {:>w3$}  {:>w4$}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                row[4],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+                w4 = widths[4],
+            )
+        };
+
+        write_row(f, &header)?;
+
+        for row in &rows {
+            write_row(f, row)?;
+        }
+
+        write_row(f, &total)
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.2} {}", value, UNITS[unit])
+    }
+}
+
+/// Registers an allocation in [`GpuMemoryStats`] while it is alive.
+pub(crate) struct MemoryAllocation {
+    key: AllocationKey,
+    bytes: u64,
+}
+
+impl MemoryAllocation {
+    pub fn new(kind: AllocationKind, category: String, label: Option<&str>, bytes: u64) -> Self {
+        let key = AllocationKey {
+            kind,
+            category,
+            label: label.map(String::from),
+        };
+
+        let mut registry = REGISTRY.lock().unwrap();
+        let usage = registry.entry(key.clone()).or_default();
+        usage.count += 1;
+        usage.bytes += bytes;
+
+        Self { key, bytes }
+    }
+
+    /// Categorized by `B`, without the module path.
+    pub fn buffer<B>(label: Option<&str>, bytes: u64) -> Self {
+        Self::new(AllocationKind::Buffer, short_type_name::<B>(), label, bytes)
+    }
+
+    /// Categorized by `P`, or by `format` for textures without a [`PixelFormat`](crate::texture::PixelFormat).
+    pub fn texture<P>(
+        label: Option<&str>,
+        size: Vector2<u32>,
+        format: TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let category = match short_type_name::<P>().as_str() {
+            "()" => format!("{:?}", format),
+            name => name.to_string(),
+        };
+
+        let bytes = format.theoretical_memory_footprint(Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        }) * sample_count as u64;
+
+        Self::new(AllocationKind::Texture, category, label, bytes)
+    }
+}
+
+impl Drop for MemoryAllocation {
+    fn drop(&mut self) {
+        let mut registry = REGISTRY.lock().unwrap();
+
+        if let Some(usage) = registry.get_mut(&self.key) {
+            usage.count -= 1;
+            usage.bytes -= self.bytes;
+
+            if usage.count == 0 {
+                registry.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// `type_name::<T>()` without module paths, e.g. `IndexBuf<3>` instead of `sifu_render::gpu_buffer::index::IndexBuf<3>`.
+fn short_type_name<T>() -> String {
+    let name = std::any::type_name::<T>();
+
+    let mut short = String::new();
+    let mut segment = String::new();
+
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            short.push(c);
+        }
+    }
+
+    short.push_str(segment.rsplit("::").next().unwrap_or_default());
+
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu_buffer::IndexBuf;
+
+    fn entry(label: &str) -> Option<MemoryStatsEntry> {
+        GpuMemoryStats::snapshot()
+            .entries
+            .into_iter()
+            .find(|entry| entry.label.as_deref() == Some(label))
+    }
+
+    #[test]
+    fn allocations_are_registered_while_alive() {
+        let first = MemoryAllocation::buffer::<IndexBuf<3>>(Some("stats_test_buffer"), 256);
+        let second = MemoryAllocation::buffer::<IndexBuf<3>>(Some("stats_test_buffer"), 128);
+
+        let live = entry("stats_test_buffer").unwrap();
+        assert_eq!(live.kind, AllocationKind::Buffer);
+        assert_eq!(live.category, "IndexBuf<3>");
+        assert_eq!(live.count, 2);
+        assert_eq!(live.bytes, 384);
+
+        drop(first);
+        let live = entry("stats_test_buffer").unwrap();
+        assert_eq!(live.count, 1);
+        assert_eq!(live.bytes, 128);
+
+        drop(second);
+        assert_eq!(entry("stats_test_buffer"), None);
+    }
+
+    #[test]
+    fn textures_without_pixel_format_use_the_texture_format() {
+        let allocation = MemoryAllocation::texture::<()>(
+            Some("stats_test_texture"),
+            Vector2::new(4, 2),
+            TextureFormat::Rgba8Unorm,
+            4,
+        );
+
+        let live = entry("stats_test_texture").unwrap();
+        assert_eq!(live.kind, AllocationKind::Texture);
+        assert_eq!(live.category, "Rgba8Unorm");
+        assert_eq!(live.bytes, 4 * 2 * 4 * 4);
+
+        drop(allocation);
+        assert_eq!(entry("stats_test_texture"), None);
+    }
+
+    #[test]
+    fn stats_print_as_a_table() {
+        let stats = GpuMemoryStats {
+            entries: vec![
+                MemoryStatsEntry {
+                    kind: AllocationKind::Buffer,
+                    category: "IndexBuf<3>".to_string(),
+                    label: Some("quad".to_string()),
+                    count: 2,
+                    bytes: 1536,
+                },
+                MemoryStatsEntry {
+                    kind: AllocationKind::Texture,
+                    category: "Rgba8".to_string(),
+                    label: None,
+                    count: 1,
+                    bytes: 512,
+                },
+            ],
+        };
+
+        let expected = [
+            "kind     category     label  count      size",
+            "Buffer   IndexBuf<3>  quad       2  1.50 KiB",
+            "Texture  Rgba8                   1     512 B",
+            "total                            3  2.00 KiB",
+        ];
+
+        assert_eq!(
+            stats.to_string(),
+            expected.map(|line| format!("{line}\n")).concat()
+        );
+        assert_eq!(stats.kind_bytes(AllocationKind::Texture), 512);
+    }
+
+    #[test]
+    fn bytes_use_binary_units() {
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.00 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+        assert_eq!(format_bytes(5 << 40), "5120.00 GiB");
+    }
+
+    #[test]
+    fn type_names_drop_module_paths() {
+        assert_eq!(short_type_name::<u32>(), "u32");
+        assert_eq!(short_type_name::<IndexBuf<3>>(), "IndexBuf<3>");
+        assert_eq!(
+            short_type_name::<Vec<Option<String>>>(),
+            "Vec<Option<String>>"
+        );
+        assert_eq!(short_type_name::<(u8, &str)>(), "(u8, &str)");
+        assert_eq!(short_type_name::<()>(), "()");
+    }
+}
@@ -0,0 +1,184 @@
+use std::ops::Range;
+
+/// A first-fit allocator for ranges of buffer elements.
+pub(super) struct FreeList {
+    /// Sorted and never adjacent, adjacent ranges are merged
+    free: Vec<Range<u32>>,
+    capacity: u32,
+}
+
+impl FreeList {
+    pub fn new(capacity: u32) -> Self {
+        let mut free = Vec::new();
+
+        if capacity > 0 {
+            free.push(0..capacity);
+        }
+
+        Self { free, capacity }
+    }
+
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the start of a free range of `len` elements.
+    pub fn allocate(&mut self, len: u32) -> Option<u32> {
+        if len == 0 {
+            return Some(0);
+        }
+
+        let idx = self
+            .free
+            .iter()
+            .position(|range| range.len() >= len as usize)?;
+        let range = &mut self.free[idx];
+        let start = range.start;
+
+        range.start += len;
+
+        if range.start == range.end {
+            self.free.remove(idx);
+        }
+
+        Some(start)
+    }
+
+    pub fn free(&mut self, range: Range<u32>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let idx = self.free.partition_point(|free| free.start < range.start);
+
+        let merges_prev = idx > 0 && self.free[idx - 1].end == range.start;
+        let merges_next = idx < self.free.len() && self.free[idx].start == range.end;
+
+        match (merges_prev, merges_next) {
+            (true, true) => {
+                self.free[idx - 1].end = self.free[idx].end;
+                self.free.remove(idx);
+            }
+            (true, false) => self.free[idx - 1].end = range.end,
+            (false, true) => self.free[idx].start = range.start,
+            (false, false) => self.free.insert(idx, range),
+        }
+    }
+
+    /// Adds the elements `capacity..new_capacity` as free space.
+    pub fn grow(&mut self, new_capacity: u32) {
+        let old_capacity = self.capacity;
+        self.capacity = new_capacity;
+
+        self.free(old_capacity..new_capacity);
+    }
+
+    /// The capacity needed so that `len` more elements fit after growing.
+    pub fn required_capacity(&self, len: u32) -> u32 {
+        // Free space at the end can be extended
+        let tail = self
+            .free
+            .last()
+            .filter(|range| range.end == self.capacity)
+            .map_or(0, |range| range.len() as u32);
+
+        self.capacity + len - tail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FreeList;
+
+    /// The free ranges as tuples, single ranges in arrays are easily mistaken for a range of values.
+    fn free(list: &FreeList) -> Vec<(u32, u32)> {
+        list.free.iter().map(|range| (range.start, range.end)).collect()
+    }
+
+    #[test]
+    fn allocates_first_fit() {
+        let mut list = FreeList::new(10);
+
+        assert_eq!(list.allocate(4), Some(0));
+        assert_eq!(list.allocate(4), Some(4));
+        assert_eq!(list.allocate(4), None);
+        assert_eq!(list.allocate(2), Some(8));
+        assert!(list.free.is_empty());
+    }
+
+    #[test]
+    fn empty_allocations_always_fit() {
+        let mut list = FreeList::new(0);
+
+        assert_eq!(list.allocate(0), Some(0));
+        assert_eq!(list.allocate(1), None);
+    }
+
+    #[test]
+    fn free_coalesces_neighbours() {
+        let mut list = FreeList::new(9);
+
+        for _ in 0..3 {
+            list.allocate(3);
+        }
+
+        list.free(0..3);
+        list.free(6..9);
+        assert_eq!(free(&list), [(0, 3), (6, 9)]);
+
+        // Merges with both sides
+        list.free(3..6);
+        assert_eq!(free(&list), [(0, 9)]);
+    }
+
+    #[test]
+    fn free_merges_with_one_side() {
+        let mut list = FreeList::new(9);
+        list.allocate(9);
+
+        list.free(3..6);
+        list.free(0..3);
+        assert_eq!(free(&list), [(0, 6)]);
+
+        list.free(7..9);
+        list.free(6..7);
+        assert_eq!(free(&list), [(0, 9)]);
+    }
+
+    #[test]
+    fn fragmented_holes_are_reused() {
+        let mut list = FreeList::new(12);
+
+        let blocks = [4, 4, 4].map(|len| list.allocate(len).unwrap());
+        list.free(blocks[0]..blocks[0] + 4);
+        list.free(blocks[2]..blocks[2] + 4);
+
+        // Neither hole fits 6 elements, smaller allocations fill the first hole first
+        assert_eq!(list.allocate(6), None);
+        assert_eq!(list.allocate(3), Some(0));
+        assert_eq!(list.allocate(4), Some(8));
+        assert_eq!(free(&list), [(3, 4)]);
+    }
+
+    #[test]
+    fn grow_extends_free_tail() {
+        let mut list = FreeList::new(8);
+        list.allocate(6);
+
+        assert_eq!(list.required_capacity(4), 10);
+
+        list.grow(10);
+        assert_eq!(list.capacity(), 10);
+        assert_eq!(free(&list), [(6, 10)]);
+        assert_eq!(list.allocate(4), Some(6));
+    }
+
+    #[test]
+    fn required_capacity_without_free_tail() {
+        let mut list = FreeList::new(8);
+        list.allocate(8);
+        list.free(0..2);
+
+        assert_eq!(list.required_capacity(4), 12);
+    }
+}
@@ -1,24 +1,331 @@
-use wgpu::{Device, RenderPass};
+use std::{any::type_name, fmt::Debug, marker::PhantomData};
 
-use crate::gpu_buffer::{GpuBuffer, IndexBuf, InstanceBuf, VertexBuf};
+use wgpu::{Device, IndexFormat, Queue, RenderPass, util::DrawIndexedIndirectArgs};
 
-use super::{MeshInstance, vertex::Vertex};
+use crate::{
+    DebugGroupExt, IndirectBuffer,
+    bounds::MeshBounds,
+    gpu_buffer::{GpuBuffer, IndexBuf, InstanceBuf, VertexBuf},
+    shader::Pipeline,
+};
 
-pub struct Mesh<V, const PRIMITIVE_SIZE: usize = 3> {
+use super::{
+    MeshInstance, MorphTarget, MorphTargets, Submesh, VertexAttributes,
+    topology::{List, MeshTopology, PrimitiveSize, Strip},
+    vertex::Vertex,
+};
+
+/// `PRIMITIVE_SIZE` is the number of indices per primitive (1 for points, 2 for lines and 3 for triangles).
+/// `K` is either [`List`] or [`Strip`], together they define the [`Topology`](super::Topology) of the mesh.
+///
+/// Meshes created with [`Mesh::new_unindexed`] have no index buffer.
+pub struct Mesh<V, const PRIMITIVE_SIZE: usize = 3, K = List> {
     vertices: GpuBuffer<V, VertexBuf>,
-    indices: GpuBuffer<u32, IndexBuf<PRIMITIVE_SIZE>>,
+    indices: Option<GpuBuffer<u32, IndexBuf<PRIMITIVE_SIZE>>>,
+    /// `None` if the mesh uses the default label, see [`Mesh::label`]
+    label: Option<String>,
+    /// Model space bounds for culling, see [`Mesh::with_bounds`]
+    bounds: Option<MeshBounds>,
+    submeshes: Vec<Submesh>,
+    morph_targets: Option<MorphTargets>,
+    _phantom: PhantomData<K>,
 }
 
-impl<V: Vertex, const C: usize> Mesh<V, C> {
-    pub fn new(device: &Device, vertices: Vec<V>, indices: Vec<[u32; C]>) -> Self {
-        let vertices = GpuBuffer::vertices(device, vertices);
-        let indices = GpuBuffer::<u32, _>::indices(device, indices);
+impl<V: Vertex, const C: usize> Mesh<V, C, List> {
+    /// The indices are stored as `u16` if all of them fit, otherwise as `u32`.
+    ///
+    /// `label` is used for the vertex and index buffer, see [`Mesh::label`].
+    pub fn new(
+        device: &Device,
+        vertices: impl IntoIterator<Item = V>,
+        indices: impl IntoIterator<Item = [u32; C]>,
+        label: Option<&str>,
+    ) -> Self {
+        let vertices = GpuBuffer::vertices(device, vertices, label);
+        let indices = GpuBuffer::<u32, _>::indices(device, indices, label);
+
+        Self::from_buffers(vertices, Some(indices), label)
+    }
+
+    /// Like [`Mesh::new`], with [`bounds`](Mesh::bounds) computed from the vertex positions.
+    pub fn new_with_bounds(
+        device: &Device,
+        vertices: impl IntoIterator<Item = V>,
+        indices: impl IntoIterator<Item = [u32; C]>,
+        label: Option<&str>,
+    ) -> Self
+    where
+        V: VertexAttributes,
+    {
+        let vertices = vertices.into_iter().collect::<Vec<_>>();
+        let bounds = MeshBounds::from_vertices(&vertices);
+
+        let mut mesh = Self::new(device, vertices, indices, label);
+        mesh.set_bounds(bounds);
+        mesh
+    }
+
+    /// Stores the indices in `format`. Accepts `u16` and `u32` indices, use `.iter().copied()` for slices.
+    ///
+    /// # Panics
+    /// If `format` is [`IndexFormat::Uint16`] and an index doesn't fit into a `u16`.
+    pub fn with_index_format<T>(
+        device: &Device,
+        vertices: impl IntoIterator<Item = V>,
+        indices: impl IntoIterator<Item = [T; C]>,
+        format: IndexFormat,
+        label: Option<&str>,
+    ) -> Self
+    where
+        T: TryInto<u16> + Into<u32> + Copy,
+        T::Error: Debug,
+    {
+        let vertices = GpuBuffer::vertices(device, vertices, label);
+        let indices = GpuBuffer::<T, _>::with_format(device, indices, format, label).cast();
+
+        Self::from_buffers(vertices, Some(indices), label)
+    }
+
+    /// Replaces all indices, switching between `u16` and `u32` indices as needed.
+    ///
+    /// Adds an index buffer to unindexed meshes.
+    pub fn update_indices(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        indices: impl IntoIterator<Item = [u32; C]>,
+    ) {
+        match &mut self.indices {
+            Some(buffer) => buffer.update(device, queue, indices),
+            None => {
+                self.indices = Some(GpuBuffer::<u32, _>::indices(
+                    device,
+                    indices,
+                    self.label.as_deref(),
+                ))
+            }
+        }
+    }
+
+    /// Overwrites the primitives starting at the primitive `first_primitive`.
+    ///
+    /// # Panics
+    /// If the mesh is unindexed, the written range exceeds the current index count or an index doesn't fit into the current index format.
+    pub fn update_indices_range(
+        &self,
+        queue: &Queue,
+        first_primitive: u32,
+        indices: impl IntoIterator<Item = [u32; C]>,
+    ) {
+        self.indices
+            .as_ref()
+            .expect("the mesh has no index buffer")
+            .update_range(queue, first_primitive, indices);
+    }
+}
+
+impl<V: Vertex, const C: usize> Mesh<V, C, Strip> {
+    /// Each strip is a list of indices, the strips are separated by primitive restarts.
+    pub fn new_strips(
+        device: &Device,
+        vertices: Vec<V>,
+        strips: Vec<Vec<u32>>,
+        label: Option<&str>,
+    ) -> Self {
+        let vertices = GpuBuffer::vertices(device, vertices, label);
+
+        let indices = strips.join(&u32::MAX);
+        let indices = GpuBuffer::indices_u32(device, &indices, label);
+
+        Self::from_buffers(vertices, Some(indices), label)
+    }
+
+    /// Replaces all strips.
+    pub fn update_strips(&mut self, device: &Device, queue: &Queue, strips: Vec<Vec<u32>>) {
+        let indices = strips.join(&u32::MAX);
+
+        match &mut self.indices {
+            Some(buffer) => buffer.update_u32(device, queue, &indices),
+            None => {
+                self.indices = Some(GpuBuffer::indices_u32(
+                    device,
+                    &indices,
+                    self.label.as_deref(),
+                ))
+            }
+        }
+    }
+}
+
+impl<V: Vertex, const C: usize, K> Mesh<V, C, K> {
+    /// A mesh without an index buffer, the vertices are drawn in order.
+    ///
+    /// For lists every `PRIMITIVE_SIZE` vertices form a primitive, for strips the vertices form a single strip.
+    pub fn new_unindexed(
+        device: &Device,
+        vertices: impl IntoIterator<Item = V>,
+        label: Option<&str>,
+    ) -> Self {
+        let vertices = GpuBuffer::vertices(device, vertices, label);
+
+        Self::from_buffers(vertices, None, label)
+    }
+
+    /// Like [`Mesh::new_unindexed`], with [`bounds`](Mesh::bounds) computed from the vertex positions.
+    pub fn new_unindexed_with_bounds(
+        device: &Device,
+        vertices: impl IntoIterator<Item = V>,
+        label: Option<&str>,
+    ) -> Self
+    where
+        V: VertexAttributes,
+    {
+        let vertices = vertices.into_iter().collect::<Vec<_>>();
+        let bounds = MeshBounds::from_vertices(&vertices);
+
+        let mut mesh = Self::new_unindexed(device, vertices, label);
+        mesh.set_bounds(bounds);
+        mesh
+    }
+
+    fn from_buffers(
+        vertices: GpuBuffer<V, VertexBuf>,
+        indices: Option<GpuBuffer<u32, IndexBuf<C>>>,
+        label: Option<&str>,
+    ) -> Self {
+        Self {
+            vertices,
+            indices,
+            label: label.map(String::from),
+            bounds: None,
+            submeshes: Vec::new(),
+            morph_targets: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Replaces all vertices. The buffer only gets reallocated if the vertices don't fit into it.
+    pub fn update_vertices(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        vertices: impl IntoIterator<Item = V>,
+    ) {
+        self.vertices.update(device, queue, vertices);
+    }
 
-        Self { vertices, indices }
+    /// Overwrites the vertices starting at `start`.
+    ///
+    /// # Panics
+    /// If the written range exceeds the current vertex count.
+    pub fn update_vertices_range(
+        &self,
+        queue: &Queue,
+        start: u32,
+        vertices: impl IntoIterator<Item = V>,
+    ) {
+        self.vertices.update_range(queue, start, vertices);
     }
 }
 
-impl<V, const C: usize> Mesh<V, C> {
+impl<V, const C: usize, K> Mesh<V, C, K> {
+    /// The debug label of the mesh, defaults to the type name of `V`.
+    ///
+    /// Draws of the mesh are wrapped in a debug group with this label.
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(type_name::<V>())
+    }
+
+    /// Sets the model space bounds used for culling.
+    ///
+    /// Meshes built from [`MeshData`](super::MeshData), [`shapes`](super::shapes), imported files or
+    /// [`Mesh::new_with_bounds`] get them from their vertex positions, other meshes have none.
+    pub fn with_bounds(self, bounds: MeshBounds) -> Self {
+        Self {
+            bounds: Some(bounds),
+            ..self
+        }
+    }
+
+    /// Has to be called when updated vertices change the bounds.
+    pub fn set_bounds(&mut self, bounds: Option<MeshBounds>) {
+        self.bounds = bounds;
+    }
+
+    pub fn bounds(&self) -> Option<&MeshBounds> {
+        self.bounds.as_ref()
+    }
+
+    /// # Panics
+    /// If a submesh exceeds the index buffer, or the vertex buffer for unindexed meshes.
+    pub fn with_submeshes(mut self, submeshes: impl IntoIterator<Item = Submesh>) -> Self {
+        for submesh in submeshes {
+            self.add_submesh(submesh);
+        }
+
+        self
+    }
+
+    /// # Panics
+    /// If the submesh exceeds the index buffer, or the vertex buffer for unindexed meshes.
+    pub fn add_submesh(&mut self, submesh: Submesh) {
+        let count = match &self.indices {
+            Some(indices) => indices.count(),
+            None => self.vertices.count(),
+        };
+
+        assert!(
+            submesh.indices.start <= submesh.indices.end && submesh.indices.end <= count,
+            "submesh {} with range {:?} exceeds the {count} indices of mesh {}",
+            submesh.name,
+            submesh.indices,
+            self.label(),
+        );
+
+        self.submeshes.push(submesh);
+    }
+
+    pub fn submeshes(&self) -> &[Submesh] {
+        &self.submeshes
+    }
+
+    /// The first submesh called `name`.
+    pub fn submesh(&self, name: &str) -> Option<&Submesh> {
+        self.submeshes.iter().find(|submesh| submesh.name == name)
+    }
+
+    /// Uploads the blend shapes of the mesh, they are applied in the vertex shader, see [`MorphTargets`].
+    ///
+    /// # Panics
+    /// If a target doesn't have a delta for every vertex or there are more than
+    /// [`MAX_MORPH_TARGETS`](super::MAX_MORPH_TARGETS) targets.
+    pub fn with_morph_targets(self, device: &Device, targets: &[MorphTarget]) -> Self {
+        let morph_targets =
+            MorphTargets::new(device, self.vertex_count(), targets, self.label.as_deref());
+
+        Self {
+            morph_targets: Some(morph_targets),
+            ..self
+        }
+    }
+
+    pub fn morph_targets(&self) -> Option<&MorphTargets> {
+        self.morph_targets.as_ref()
+    }
+
+    pub fn vertex_count(&self) -> u32 {
+        self.vertices.count()
+    }
+
+    /// The number of indices, 0 for unindexed meshes.
+    pub fn index_count(&self) -> u32 {
+        self.indices.as_ref().map_or(0, |indices| indices.count())
+    }
+
+    pub fn is_indexed(&self) -> bool {
+        self.indices.is_some()
+    }
+
     pub fn draw<'a, I>(
         &'a self,
         instances: &'a GpuBuffer<I, InstanceBuf>,
@@ -26,12 +333,162 @@ impl<V, const C: usize> Mesh<V, C> {
     ) where
         I: MeshInstance<Vertex = V>,
     {
-        pass.set_index_buffer(self.indices.slice(), self.indices.index_format());
+        let mut pass = pass.debug_group(self.label());
+        self.set_buffers(instances, &mut pass);
+
+        match &self.indices {
+            Some(indices) => pass.draw_indexed(0..indices.count(), 0, 0..instances.count()),
+            None => pass.draw(0..self.vertices.count(), 0..instances.count()),
+        }
+    }
+
+    /// Draws only the range of `submesh`, which doesn't have to be one of [`Mesh::submeshes`].
+    pub fn draw_submesh<'a, I>(
+        &'a self,
+        submesh: &Submesh,
+        instances: &'a GpuBuffer<I, InstanceBuf>,
+        pass: &mut RenderPass<'a>,
+    ) where
+        I: MeshInstance<Vertex = V>,
+    {
+        let mut pass = pass.debug_group(&submesh.name);
+        self.set_buffers(instances, &mut pass);
+        self.draw_range(submesh, instances.count(), &mut pass);
+    }
+
+    /// Draws every submesh, `bind` is called before each draw to set e.g. the bind group of its material.
+    pub fn draw_submeshes<'a, I>(
+        &'a self,
+        instances: &'a GpuBuffer<I, InstanceBuf>,
+        pass: &mut RenderPass<'a>,
+        mut bind: impl FnMut(usize, &Submesh, &mut RenderPass<'a>),
+    ) where
+        I: MeshInstance<Vertex = V>,
+    {
+        let mut pass = pass.debug_group(self.label());
+        self.set_buffers(instances, &mut pass);
+
+        for (idx, submesh) in self.submeshes.iter().enumerate() {
+            let mut pass = pass.debug_group(&submesh.name);
+
+            bind(idx, submesh, &mut pass);
+            self.draw_range(submesh, instances.count(), &mut pass);
+        }
+    }
+
+    fn draw_range(&self, submesh: &Submesh, instance_count: u32, pass: &mut RenderPass) {
+        match &self.indices {
+            Some(_) => pass.draw_indexed(
+                submesh.indices.clone(),
+                submesh.base_vertex,
+                0..instance_count,
+            ),
+            None => pass.draw(submesh.indices.clone(), 0..instance_count),
+        }
+    }
+
+    /// Draws the mesh with the arguments at `index` of `indirect`, which can be written by a compute shader.
+    ///
+    /// # Panics
+    /// If the mesh is unindexed or `index` is out of bounds of `indirect`.
+    pub fn draw_indirect<'a, I>(
+        &'a self,
+        instances: &'a GpuBuffer<I, InstanceBuf>,
+        indirect: &'a IndirectBuffer,
+        index: u32,
+        pass: &mut RenderPass<'a>,
+    ) where
+        I: MeshInstance<Vertex = V>,
+    {
+        self.indexed();
+
+        assert!(
+            index < indirect.count(),
+            "indirect draw {index} out of bounds, the buffer has {} draws",
+            indirect.count(),
+        );
+
+        let mut pass = pass.debug_group(self.label());
+        self.set_buffers(instances, &mut pass);
+        pass.draw_indexed_indirect(indirect.buffer(), indirect.offset(index));
+    }
+
+    /// Issues one draw per argument in `indirect`, starting at `first`.
+    ///
+    /// # Panics
+    /// If the mesh is unindexed or `first..first + count` exceeds the draws of `indirect`.
+    pub fn multi_draw_indexed_indirect<'a, I>(
+        &'a self,
+        instances: &'a GpuBuffer<I, InstanceBuf>,
+        indirect: &'a IndirectBuffer,
+        first: u32,
+        count: u32,
+        pass: &mut RenderPass<'a>,
+    ) where
+        I: MeshInstance<Vertex = V>,
+    {
+        self.indexed();
+
+        assert!(
+            first as u64 + count as u64 <= indirect.count() as u64,
+            "indirect draws {first}..{} out of bounds, the buffer has {} draws",
+            first as u64 + count as u64,
+            indirect.count(),
+        );
+
+        let mut pass = pass.debug_group(self.label());
+        self.set_buffers(instances, &mut pass);
+        pass.multi_draw_indexed_indirect(indirect.buffer(), indirect.offset(first), count);
+    }
+
+    /// The indirect arguments that draw the whole mesh `instance_count` times.
+    ///
+    /// # Panics
+    /// If the mesh is unindexed.
+    pub fn indirect_args(&self, instance_count: u32) -> DrawIndexedIndirectArgs {
+        DrawIndexedIndirectArgs {
+            index_count: self.indexed().count(),
+            instance_count,
+            first_index: 0,
+            base_vertex: 0,
+            first_instance: 0,
+        }
+    }
+
+    fn indexed(&self) -> &GpuBuffer<u32, IndexBuf<C>> {
+        self.indices
+            .as_ref()
+            .expect("indexed indirect draws need a mesh with an index buffer")
+    }
+
+    fn set_buffers<'a, I>(
+        &'a self,
+        instances: &'a GpuBuffer<I, InstanceBuf>,
+        pass: &mut RenderPass<'a>,
+    ) {
+        if let Some(indices) = &self.indices {
+            pass.set_index_buffer(indices.slice(), indices.index_format());
+        }
 
         // Vertices
         pass.set_vertex_buffer(0, self.vertices.slice());
         // Instance
         pass.set_vertex_buffer(1, instances.slice());
-        pass.draw_indexed(0..self.indices.count(), 0, 0..instances.count());
+    }
+
+    /// Sets `pipeline` and draws the mesh.
+    ///
+    /// The topology of `pipeline` has to match the topology of the mesh.
+    pub fn draw_with<'a, I>(
+        &'a self,
+        pipeline: &'a Pipeline<<PrimitiveSize<C> as MeshTopology<K>>::Topology>,
+        instances: &'a GpuBuffer<I, InstanceBuf>,
+        pass: &mut RenderPass<'a>,
+    ) where
+        I: MeshInstance<Vertex = V>,
+        PrimitiveSize<C>: MeshTopology<K>,
+    {
+        pass.set_pipeline(pipeline.pipeline());
+        self.draw(instances, pass);
     }
 }
@@ -0,0 +1,76 @@
+use cgmath::{Vector2, Vector3, Vector4, Zero};
+
+/// The attributes of a single vertex of an imported or generated mesh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshAttributes {
+    pub position: Vector3<f32>,
+    /// `None` if the source has no normal for this vertex
+    pub normal: Option<Vector3<f32>>,
+    /// `None` if the source has no texture coordinates for this vertex
+    pub uv: Option<Vector2<f32>>,
+    /// `xyz` points along increasing `u`, `w` is the handedness: `cross(normal, tangent.xyz) * w` points along increasing `v`.
+    /// `None` if the source has no tangent for this vertex
+    pub tangent: Option<Vector4<f32>>,
+    /// The indices of the joints influencing a skinned vertex, see [`Skeleton`](crate::animation::Skeleton)
+    pub joints: Option<Vector4<u32>>,
+    /// The weights of [`MeshAttributes::joints`], summing up to 1
+    pub weights: Option<Vector4<f32>>,
+}
+
+impl MeshAttributes {
+    pub fn new(position: Vector3<f32>) -> Self {
+        Self {
+            position,
+            normal: None,
+            uv: None,
+            tangent: None,
+            joints: None,
+            weights: None,
+        }
+    }
+
+    /// The normal, or zero if there is none.
+    pub fn normal_or_zero(&self) -> Vector3<f32> {
+        self.normal.unwrap_or_else(Vector3::zero)
+    }
+
+    /// The texture coordinates, or zero if there are none.
+    pub fn uv_or_zero(&self) -> Vector2<f32> {
+        self.uv.unwrap_or_else(Vector2::zero)
+    }
+
+    /// The tangent, or zero if there is none.
+    pub fn tangent_or_zero(&self) -> Vector4<f32> {
+        self.tangent.unwrap_or_else(Vector4::zero)
+    }
+
+    /// The joint indices, or zero if there are none.
+    pub fn joints_or_zero(&self) -> Vector4<u32> {
+        self.joints.unwrap_or_else(Vector4::zero)
+    }
+
+    /// The joint weights, or zero if there are none.
+    pub fn weights_or_zero(&self) -> Vector4<f32> {
+        self.weights.unwrap_or_else(Vector4::zero)
+    }
+}
+
+/// Builds a vertex from the attributes of imported or generated meshes.
+///
+/// Can be derived, fields marked with `#[position]`, `#[normal]`, `#[uv]`, `#[tangent]`, `#[joints]` or `#[weights]` get the
+/// matching attribute converted with `Into`, all other fields are set to [`Default::default`]. Missing attributes are zero.
+///
+/// ```ignore
+/// #[derive(Vertex, FromMeshAttributes)]
+/// struct MyVertex {
+///     #[position]
+///     position: Vector3<f32>,
+///     #[normal]
+///     normal: Vector3<f32>,
+///     #[uv]
+///     uv: Vector2<f32>,
+/// }
+/// ```
+pub trait FromMeshAttributes {
+    fn from_mesh_attributes(attributes: &MeshAttributes) -> Self;
+}
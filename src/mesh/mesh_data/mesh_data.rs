@@ -0,0 +1,139 @@
+use cgmath::{InnerSpace, Matrix, Matrix3, Matrix4, SquareMatrix};
+use wgpu::Device;
+
+use crate::{
+    bounds::MeshBounds,
+    mesh::{FromMeshAttributes, Mesh, MeshAttributes, Vertex, VertexAttributes},
+};
+
+/// A triangle mesh in CPU memory that can be processed before it is uploaded as a [`Mesh`].
+///
+/// Processing the attributes of the vertices requires [`VertexAttributes`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshData<V> {
+    pub vertices: Vec<V>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+impl<V> Default for MeshData<V> {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            triangles: Vec::new(),
+        }
+    }
+}
+
+impl<V> MeshData<V> {
+    pub fn new(vertices: Vec<V>, triangles: Vec<[u32; 3]>) -> Self {
+        Self {
+            vertices,
+            triangles,
+        }
+    }
+
+    /// Moves the vertices and triangles of `other` into this mesh.
+    pub fn append(&mut self, other: MeshData<V>) {
+        let offset = self.vertices.len() as u32;
+
+        self.vertices.extend(other.vertices);
+        self.triangles.extend(
+            other
+                .triangles
+                .into_iter()
+                .map(|triangle| triangle.map(|idx| idx + offset)),
+        );
+    }
+
+    /// Combines all `meshes` into a single one.
+    pub fn merge(meshes: impl IntoIterator<Item = MeshData<V>>) -> Self {
+        let mut merged = Self::default();
+
+        for mesh in meshes {
+            merged.append(mesh);
+        }
+
+        merged
+    }
+
+    /// Converts the vertices, keeping the triangles.
+    pub fn map<W>(self, f: impl FnMut(V) -> W) -> MeshData<W> {
+        MeshData {
+            vertices: self.vertices.into_iter().map(f).collect(),
+            triangles: self.triangles,
+        }
+    }
+}
+
+impl<V: FromMeshAttributes> MeshData<V> {
+    /// Builds the vertices from imported or generated attributes.
+    pub fn from_attributes(vertices: &[MeshAttributes], triangles: Vec<[u32; 3]>) -> Self {
+        Self {
+            vertices: vertices.iter().map(V::from_mesh_attributes).collect(),
+            triangles,
+        }
+    }
+}
+
+impl<V: VertexAttributes> MeshData<V> {
+    /// The bounds of all vertices, `None` if there are none.
+    pub fn bounds(&self) -> Option<MeshBounds> {
+        MeshBounds::from_vertices(&self.vertices)
+    }
+
+    /// Uploads the mesh with its [`bounds`](MeshData::bounds).
+    pub fn upload(&self, device: &Device, label: Option<&str>) -> Mesh<V>
+    where
+        V: Vertex,
+    {
+        let mesh = Mesh::new(
+            device,
+            self.vertices.iter().cloned(),
+            self.triangles.iter().copied(),
+            label,
+        );
+
+        match self.bounds() {
+            Some(bounds) => mesh.with_bounds(bounds),
+            None => mesh,
+        }
+    }
+
+    /// Transforms the positions, normals and tangents by the affine `transform`.
+    ///
+    /// Mirroring transforms flip the winding order of the triangles, so front faces stay front faces.
+    pub fn transform(&mut self, transform: Matrix4<f32>) {
+        let linear = Matrix3::from_cols(
+            transform.x.truncate(),
+            transform.y.truncate(),
+            transform.z.truncate(),
+        );
+        let normal_matrix = linear
+            .invert()
+            .map_or(linear, |inverse| inverse.transpose());
+        let mirrored = linear.determinant() < 0.0;
+
+        for vertex in &mut self.vertices {
+            vertex.set_position((transform * vertex.position().extend(1.0)).truncate());
+
+            if let Some(normal) = vertex.normal() {
+                vertex.set_normal((normal_matrix * normal).normalize());
+            }
+
+            if let Some(tangent) = vertex.tangent() {
+                let handedness = match mirrored {
+                    true => -tangent.w,
+                    false => tangent.w,
+                };
+
+                vertex.set_tangent((linear * tangent.truncate()).normalize().extend(handedness));
+            }
+        }
+
+        if mirrored {
+            for [_, b, c] in &mut self.triangles {
+                std::mem::swap(b, c);
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod mesh_data;
+mod normals;
+mod optimize;
+#[cfg(feature = "tangents")]
+mod tangents;
+mod weld;
+
+pub use mesh_data::MeshData;
@@ -0,0 +1,61 @@
+use cgmath::{InnerSpace, Vector3, Zero};
+
+use crate::mesh::VertexAttributes;
+
+use super::MeshData;
+
+impl<V: VertexAttributes> MeshData<V> {
+    /// Sets the normal of every vertex to the area weighted average of the triangles using it.
+    ///
+    /// Vertices with the same position but a different index keep separate normals, [`MeshData::weld`] them first to
+    /// smooth over such seams.
+    pub fn compute_smooth_normals(&mut self) {
+        let mut normals = vec![Vector3::zero(); self.vertices.len()];
+
+        for triangle in &self.triangles {
+            // The length of the cross product is twice the area
+            let normal = self.face_normal(*triangle);
+
+            for idx in triangle {
+                normals[*idx as usize] += normal;
+            }
+        }
+
+        for (vertex, normal) in self.vertices.iter_mut().zip(normals) {
+            if normal.magnitude2() > 0.0 {
+                vertex.set_normal(normal.normalize());
+            }
+        }
+    }
+
+    /// Gives every triangle its own vertices with the normal of the triangle.
+    pub fn compute_flat_normals(&mut self) {
+        let mut vertices = Vec::with_capacity(self.triangles.len() * 3);
+
+        for triangle in &self.triangles {
+            let normal = self.face_normal(*triangle);
+
+            for idx in triangle {
+                let mut vertex = self.vertices[*idx as usize].clone();
+
+                if normal.magnitude2() > 0.0 {
+                    vertex.set_normal(normal.normalize());
+                }
+
+                vertices.push(vertex);
+            }
+        }
+
+        self.triangles = (0..self.triangles.len() as u32)
+            .map(|triangle| [0, 1, 2].map(|corner| triangle * 3 + corner))
+            .collect();
+        self.vertices = vertices;
+    }
+
+    /// The unnormalized normal of a counter-clockwise triangle.
+    pub(super) fn face_normal(&self, triangle: [u32; 3]) -> Vector3<f32> {
+        let [a, b, c] = triangle.map(|idx| self.vertices[idx as usize].position());
+
+        (b - a).cross(c - a)
+    }
+}
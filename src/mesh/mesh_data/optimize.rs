@@ -0,0 +1,494 @@
+use cgmath::{InnerSpace, Vector3, Zero};
+
+use crate::mesh::VertexAttributes;
+
+use super::MeshData;
+
+/// The size of the LRU cache modeled by [`MeshData::optimize_vertex_cache`].
+const LRU_CACHE_SIZE: usize = 32;
+
+/// The size of the FIFO cache modeled by [`MeshData::optimize_overdraw`].
+const FIFO_CACHE_SIZE: usize = 16;
+
+impl<V> MeshData<V> {
+    /// Reorders the triangles to reuse recently transformed vertices, using Tom Forsyth's linear-speed vertex cache
+    /// optimisation.
+    pub fn optimize_vertex_cache(&mut self) {
+        let vertex_count = self.vertices.len();
+
+        // The triangles of every vertex, the first `remaining` of them are not emitted yet
+        let mut offsets = vec![0; vertex_count + 1];
+        for idx in self.triangles.iter().flatten() {
+            offsets[*idx as usize + 1] += 1;
+        }
+        for idx in 0..vertex_count {
+            offsets[idx + 1] += offsets[idx];
+        }
+
+        let mut remaining = (0..vertex_count)
+            .map(|idx| offsets[idx + 1] - offsets[idx])
+            .collect::<Vec<_>>();
+        let mut vertex_triangles = vec![0; offsets[vertex_count]];
+        let mut filled = vec![0; vertex_count];
+
+        for (triangle_idx, triangle) in self.triangles.iter().enumerate() {
+            for idx in triangle.map(|idx| idx as usize) {
+                vertex_triangles[offsets[idx] + filled[idx]] = triangle_idx as u32;
+                filled[idx] += 1;
+            }
+        }
+
+        let mut cache_positions = vec![None; vertex_count];
+        let mut vertex_scores = (0..vertex_count)
+            .map(|idx| forsyth_score(None, remaining[idx]))
+            .collect::<Vec<_>>();
+
+        let triangle_score = |triangle: &[u32; 3], vertex_scores: &[f32]| {
+            triangle
+                .iter()
+                .map(|idx| vertex_scores[*idx as usize])
+                .sum()
+        };
+
+        let mut triangle_scores = self
+            .triangles
+            .iter()
+            .map(|triangle| triangle_score(triangle, &vertex_scores))
+            .collect::<Vec<f32>>();
+
+        let mut emitted = vec![false; self.triangles.len()];
+        let mut order = Vec::with_capacity(self.triangles.len());
+        let mut cache = Vec::<u32>::with_capacity(LRU_CACHE_SIZE + 3);
+        // Triangles before the cursor are emitted, used when no triangle touches the cache
+        let mut cursor = 0;
+
+        let mut best = (0..self.triangles.len())
+            .max_by(|a, b| triangle_scores[*a].total_cmp(&triangle_scores[*b]));
+
+        while let Some(triangle_idx) = best {
+            emitted[triangle_idx] = true;
+            order.push(self.triangles[triangle_idx]);
+
+            let triangle = self.triangles[triangle_idx];
+
+            for idx in triangle {
+                let vertex = idx as usize;
+                let active =
+                    &mut vertex_triangles[offsets[vertex]..offsets[vertex] + remaining[vertex]];
+
+                if let Some(position) = active
+                    .iter()
+                    .position(|other| *other == triangle_idx as u32)
+                {
+                    let last = active.len() - 1;
+                    active.swap(position, last);
+                    remaining[vertex] -= 1;
+                }
+            }
+
+            // Moves the vertices of the triangle to the front of the cache
+            let mut new_cache = triangle.to_vec();
+            new_cache.extend(cache.iter().filter(|idx| !triangle.contains(idx)));
+
+            for evicted in new_cache.drain(LRU_CACHE_SIZE.min(new_cache.len())..) {
+                cache_positions[evicted as usize] = None;
+                vertex_scores[evicted as usize] = forsyth_score(None, remaining[evicted as usize]);
+            }
+
+            cache = new_cache;
+
+            for (position, idx) in cache.iter().enumerate() {
+                cache_positions[*idx as usize] = Some(position);
+                vertex_scores[*idx as usize] =
+                    forsyth_score(Some(position), remaining[*idx as usize]);
+            }
+
+            best = None;
+            let mut best_score = f32::MIN;
+
+            for idx in &cache {
+                let vertex = *idx as usize;
+
+                for other in &vertex_triangles[offsets[vertex]..offsets[vertex] + remaining[vertex]]
+                {
+                    let other = *other as usize;
+                    let score = triangle_score(&self.triangles[other], &vertex_scores);
+                    triangle_scores[other] = score;
+
+                    if score > best_score {
+                        best = Some(other);
+                        best_score = score;
+                    }
+                }
+            }
+
+            if best.is_none() {
+                while cursor < emitted.len() && emitted[cursor] {
+                    cursor += 1;
+                }
+
+                best = (cursor < emitted.len()).then_some(cursor);
+            }
+        }
+
+        self.triangles = order;
+    }
+
+    /// Reorders the vertices by their first use and removes the ones no triangle uses.
+    pub fn optimize_vertex_fetch(&mut self) {
+        let mut order = Vec::with_capacity(self.vertices.len());
+        let mut used = vec![false; self.vertices.len()];
+
+        for idx in self.triangles.iter().flatten() {
+            if !used[*idx as usize] {
+                used[*idx as usize] = true;
+                order.push(*idx);
+            }
+        }
+
+        self.reorder_vertices(order);
+    }
+
+    /// Removes the vertices no triangle uses, keeping the order of the others.
+    pub(super) fn remove_unused_vertices(&mut self) {
+        let mut used = vec![false; self.vertices.len()];
+
+        for idx in self.triangles.iter().flatten() {
+            used[*idx as usize] = true;
+        }
+
+        let order = (0..self.vertices.len() as u32)
+            .filter(|idx| used[*idx as usize])
+            .collect();
+
+        self.reorder_vertices(order);
+    }
+
+    /// Keeps the vertices at `order` in that order, the triangles may only use those.
+    fn reorder_vertices(&mut self, order: Vec<u32>) {
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+
+        for (new_idx, old_idx) in order.iter().enumerate() {
+            remap[*old_idx as usize] = new_idx as u32;
+        }
+
+        let mut vertices = std::mem::take(&mut self.vertices)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        self.vertices = order
+            .iter()
+            .map(|idx| {
+                vertices[*idx as usize]
+                    .take()
+                    .expect("vertices are kept once")
+            })
+            .collect();
+
+        for idx in self.triangles.iter_mut().flatten() {
+            *idx = remap[*idx as usize];
+        }
+    }
+}
+
+impl<V: VertexAttributes> MeshData<V> {
+    /// Reorders clusters of triangles so outward facing ones, which likely occlude others, are drawn first.
+    ///
+    /// Run it after [`MeshData::optimize_vertex_cache`]. `threshold` is how much worse the vertex cache efficiency may
+    /// get to allow for smaller clusters, e.g. `1.05`.
+    pub fn optimize_overdraw(&mut self, threshold: f32) {
+        if self.triangles.is_empty() {
+            return;
+        }
+
+        let clusters = self.overdraw_clusters(threshold);
+
+        let mut mesh_area = 0.0;
+        let mut mesh_centroid = Vector3::zero();
+
+        let mut keyed_clusters = clusters
+            .windows(2)
+            .map(|range| {
+                let mut area = 0.0;
+                let mut centroid = Vector3::zero();
+                let mut normal = Vector3::zero();
+
+                for triangle in &self.triangles[range[0]..range[1]] {
+                    let [a, b, c] = triangle.map(|idx| self.vertices[idx as usize].position());
+                    let face_normal = (b - a).cross(c - a);
+                    let face_area = face_normal.magnitude();
+
+                    area += face_area;
+                    centroid += (a + b + c) / 3.0 * face_area;
+                    normal += face_normal;
+                }
+
+                mesh_area += area;
+                mesh_centroid += centroid;
+
+                let centroid = match area > 0.0 {
+                    true => centroid / area,
+                    false => centroid,
+                };
+
+                let normal = match normal.magnitude2() > 0.0 {
+                    true => normal.normalize(),
+                    false => normal,
+                };
+
+                (range[0]..range[1], centroid, normal)
+            })
+            .collect::<Vec<_>>();
+
+        if mesh_area > 0.0 {
+            mesh_centroid /= mesh_area;
+        }
+
+        // Clusters far out along their normal first
+        keyed_clusters.sort_by(|(_, centroid_a, normal_a), (_, centroid_b, normal_b)| {
+            let key_a = (centroid_a - mesh_centroid).dot(*normal_a);
+            let key_b = (centroid_b - mesh_centroid).dot(*normal_b);
+
+            key_b.total_cmp(&key_a)
+        });
+
+        self.triangles = keyed_clusters
+            .into_iter()
+            .flat_map(|(range, _, _)| self.triangles[range].to_vec())
+            .collect();
+    }
+
+    /// The boundaries of the clusters, starting with 0 and ending with the triangle count.
+    fn overdraw_clusters(&self, threshold: f32) -> Vec<usize> {
+        let mut cache = FifoCache::new(self.vertices.len());
+
+        // A triangle without cached vertices starts a new patch of the mesh
+        let mut hard_boundaries = Vec::new();
+
+        for (idx, triangle) in self.triangles.iter().enumerate() {
+            if cache.insert(*triangle) == 3 || idx == 0 {
+                hard_boundaries.push(idx);
+            }
+        }
+
+        hard_boundaries.push(self.triangles.len());
+
+        let mut boundaries = Vec::new();
+
+        for range in hard_boundaries.windows(2) {
+            let (start, end) = (range[0], range[1]);
+
+            cache.flush();
+            let cluster_misses = self.triangles[start..end]
+                .iter()
+                .map(|triangle| cache.insert(*triangle))
+                .sum::<u32>();
+            let target = threshold * cluster_misses as f32 / (end - start) as f32;
+
+            boundaries.push(start);
+            cache.flush();
+
+            let (mut misses, mut triangles) = (0, 0);
+
+            // Splits as soon as the running miss ratio is good enough
+            for idx in start..end {
+                misses += cache.insert(self.triangles[idx]);
+                triangles += 1;
+
+                if idx + 1 < end && misses as f32 / triangles as f32 <= target {
+                    boundaries.push(idx + 1);
+                    cache.flush();
+                    (misses, triangles) = (0, 0);
+                }
+            }
+        }
+
+        boundaries.push(self.triangles.len());
+        boundaries
+    }
+}
+
+/// The score of a vertex at `cache_position` used by `remaining` triangles that are not emitted yet.
+fn forsyth_score(cache_position: Option<usize>, remaining: usize) -> f32 {
+    if remaining == 0 {
+        return -1.0;
+    }
+
+    let cache_score = match cache_position {
+        None => 0.0,
+        // The last triangle's vertices get a fixed score so it isn't reused right away
+        Some(position) if position < 3 => 0.75,
+        Some(position) => (1.0 - (position - 3) as f32 / (LRU_CACHE_SIZE - 3) as f32).powf(1.5),
+    };
+
+    // Prefers vertices with few remaining triangles to get rid of them
+    cache_score + 2.0 * (remaining as f32).powf(-0.5)
+}
+
+/// Simulates a FIFO post-transform cache with timestamps.
+struct FifoCache {
+    timestamps: Vec<usize>,
+    time: usize,
+}
+
+impl FifoCache {
+    fn new(vertex_count: usize) -> Self {
+        Self {
+            timestamps: vec![0; vertex_count],
+            time: FIFO_CACHE_SIZE + 1,
+        }
+    }
+
+    fn flush(&mut self) {
+        self.time += FIFO_CACHE_SIZE + 1;
+    }
+
+    /// Returns the number of cache misses.
+    fn insert(&mut self, triangle: [u32; 3]) -> u32 {
+        let mut misses = 0;
+
+        for idx in triangle {
+            let timestamp = &mut self.timestamps[idx as usize];
+
+            if self.time - *timestamp > FIFO_CACHE_SIZE {
+                *timestamp = self.time;
+                self.time += 1;
+                misses += 1;
+            }
+        }
+
+        misses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Vector3;
+
+    use crate::mesh::{
+        MeshAttributes, MeshData,
+        shapes::{Shape, Torus},
+    };
+
+    use super::FifoCache;
+
+    fn torus() -> MeshData<MeshAttributes> {
+        let mesh = Torus::new(1.0, 0.25).generate();
+
+        MeshData::new(mesh.vertices, mesh.triangles)
+    }
+
+    /// A deterministic Fisher-Yates shuffle.
+    fn shuffle<T>(items: &mut [T]) {
+        let mut state = 0x2545_f491_u32;
+
+        for idx in (1..items.len()).rev() {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            items.swap(idx, (state >> 8) as usize % (idx + 1));
+        }
+    }
+
+    /// The average number of vertices transformed per triangle with a FIFO cache.
+    fn acmr(mesh: &MeshData<MeshAttributes>) -> f32 {
+        let mut cache = FifoCache::new(mesh.vertices.len());
+        let misses = mesh
+            .triangles
+            .iter()
+            .map(|triangle| cache.insert(*triangle))
+            .sum::<u32>();
+
+        misses as f32 / mesh.triangles.len() as f32
+    }
+
+    /// The triangles rotated to start with their smallest index, keeping the winding, and sorted.
+    fn canonical(mesh: &MeshData<MeshAttributes>) -> Vec<[u32; 3]> {
+        let mut triangles = mesh
+            .triangles
+            .iter()
+            .map(|triangle| {
+                let start = (0..3).min_by_key(|&idx| triangle[idx]).unwrap();
+
+                [0, 1, 2].map(|idx| triangle[(start + idx) % 3])
+            })
+            .collect::<Vec<_>>();
+
+        triangles.sort();
+        triangles
+    }
+
+    #[test]
+    fn vertex_cache_permutes_triangles() {
+        let mut mesh = torus();
+        let expected = canonical(&mesh);
+
+        mesh.optimize_vertex_cache();
+
+        assert_eq!(canonical(&mesh), expected);
+    }
+
+    #[test]
+    fn vertex_cache_lowers_acmr() {
+        let mut mesh = torus();
+        shuffle(&mut mesh.triangles);
+        let shuffled = acmr(&mesh);
+
+        mesh.optimize_vertex_cache();
+        let optimized = acmr(&mesh);
+
+        assert!(shuffled > 2.0, "shuffled ACMR {shuffled}");
+        assert!(optimized < 1.0, "optimized ACMR {optimized}");
+    }
+
+    #[test]
+    fn vertex_fetch_orders_by_first_use() {
+        let mut mesh = torus();
+        shuffle(&mut mesh.triangles);
+        mesh.vertices
+            .push(MeshAttributes::new(Vector3::new(9.0, 9.0, 9.0)));
+
+        let positions = |mesh: &MeshData<MeshAttributes>| {
+            mesh.triangles
+                .iter()
+                .map(|triangle| triangle.map(|idx| mesh.vertices[idx as usize].position))
+                .collect::<Vec<_>>()
+        };
+        let expected = positions(&mesh);
+
+        mesh.optimize_vertex_fetch();
+
+        assert_eq!(positions(&mesh), expected);
+        assert_eq!(mesh.vertices.len(), torus().vertices.len());
+
+        let mut next = 0;
+
+        for idx in mesh.triangles.iter().flatten() {
+            assert!(*idx <= next, "vertex {idx} is used before vertex {next}");
+
+            if *idx == next {
+                next += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn overdraw_permutes_triangles() {
+        let mut mesh = torus();
+        let expected = canonical(&mesh);
+
+        mesh.optimize_vertex_cache();
+        mesh.optimize_overdraw(1.05);
+
+        assert_eq!(canonical(&mesh), expected);
+    }
+
+    #[test]
+    fn optimizing_empty_mesh() {
+        let mut mesh = MeshData::<MeshAttributes>::new(Vec::new(), Vec::new());
+
+        mesh.optimize_vertex_cache();
+        mesh.optimize_overdraw(1.05);
+
+        assert!(mesh.triangles.is_empty());
+    }
+}
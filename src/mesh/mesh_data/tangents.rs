@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use cgmath::{Vector2, Vector3, Vector4, Zero};
+
+use crate::mesh::VertexAttributes;
+
+use super::MeshData;
+
+/// The triangles of a [`MeshData`] as seen by the MikkTSpace implementation, collecting a tangent per triangle corner.
+struct MikkTSpaceGeometry<'a, V> {
+    mesh: &'a MeshData<V>,
+    tangents: Vec<Vector4<f32>>,
+}
+
+impl<V: VertexAttributes> MikkTSpaceGeometry<'_, V> {
+    fn vertex(&self, face: usize, vert: usize) -> &V {
+        &self.mesh.vertices[self.mesh.triangles[face][vert] as usize]
+    }
+}
+
+impl<V: VertexAttributes> bevy_mikktspace::Geometry for MikkTSpaceGeometry<'_, V> {
+    fn num_faces(&self) -> usize {
+        self.mesh.triangles.len()
+    }
+
+    fn num_vertices_of_face(&self, _face: usize) -> usize {
+        3
+    }
+
+    fn position(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.vertex(face, vert).position().into()
+    }
+
+    fn normal(&self, face: usize, vert: usize) -> [f32; 3] {
+        self.vertex(face, vert)
+            .normal()
+            .unwrap_or_else(Vector3::zero)
+            .into()
+    }
+
+    fn tex_coord(&self, face: usize, vert: usize) -> [f32; 2] {
+        self.vertex(face, vert)
+            .uv()
+            .unwrap_or_else(Vector2::zero)
+            .into()
+    }
+
+    fn set_tangent_encoded(&mut self, tangent: [f32; 4], face: usize, vert: usize) {
+        self.tangents[face * 3 + vert] = tangent.into();
+    }
+}
+
+impl<V: VertexAttributes> MeshData<V> {
+    /// Generates MikkTSpace tangents, the standard used by glTF and most bakers, from the normals and texture coordinates.
+    ///
+    /// Missing normals and texture coordinates are zero. Every vertex keeps its index, vertices whose triangles need
+    /// different tangents are split by appending copies. Vertices no triangle uses get a zero tangent.
+    pub fn compute_tangents(&mut self) {
+        let mut geometry = MikkTSpaceGeometry {
+            mesh: self,
+            tangents: vec![Vector4::new(1.0, 0.0, 0.0, 1.0); self.triangles.len() * 3],
+        };
+
+        if !bevy_mikktspace::generate_tangents(&mut geometry) {
+            return;
+        }
+
+        let tangents = geometry.tangents;
+
+        let mut assigned = vec![false; self.vertices.len()];
+        let mut split = HashMap::<(u32, [u32; 4]), u32>::new();
+
+        for (corner, idx) in self.triangles.iter_mut().flatten().enumerate() {
+            let tangent = tangents[corner];
+            let key = (*idx, tangent.map(f32::to_bits).into());
+
+            if let Some(split_idx) = split.get(&key) {
+                *idx = *split_idx;
+                continue;
+            }
+
+            let vertex_idx = *idx as usize;
+
+            if !assigned[vertex_idx] {
+                assigned[vertex_idx] = true;
+                self.vertices[vertex_idx].set_tangent(tangent);
+            } else {
+                let mut vertex = self.vertices[vertex_idx].clone();
+                vertex.set_tangent(tangent);
+                self.vertices.push(vertex);
+
+                *idx = self.vertices.len() as u32 - 1;
+            }
+
+            split.insert(key, *idx);
+        }
+
+        for (vertex, assigned) in self.vertices.iter_mut().zip(assigned) {
+            if !assigned {
+                vertex.set_tangent(Vector4::zero());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{InnerSpace, Vector2, Vector3, Vector4, Zero};
+
+    use crate::mesh::{MeshAttributes, MeshData, VertexAttributes};
+
+    fn vertex(x: f32, y: f32) -> MeshAttributes {
+        let mut vertex = MeshAttributes::new(Vector3::new(x, y, 0.0));
+        vertex.set_normal(Vector3::unit_z());
+        vertex.set_uv(Vector2::new(x, y));
+        vertex
+    }
+
+    #[test]
+    fn vertices_keep_their_index() {
+        let unused = vertex(5.0, 5.0);
+
+        let mut mesh = MeshData::new(
+            vec![unused, vertex(0.0, 0.0), vertex(1.0, 0.0), vertex(0.0, 1.0)],
+            vec![[1, 2, 3]],
+        );
+
+        mesh.compute_tangents();
+
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.triangles, [[1, 2, 3]]);
+        assert_eq!(mesh.vertices[0].position(), unused.position());
+        assert_eq!(mesh.vertices[0].tangent(), Some(Vector4::zero()));
+
+        for vertex in &mesh.vertices[1..] {
+            let tangent = vertex.tangent().unwrap();
+            assert!((tangent - Vector4::new(1.0, 0.0, 0.0, 1.0)).magnitude2() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn mirrored_uvs_split_vertices() {
+        let mut mirrored = vertex(-1.0, 0.0);
+        mirrored.set_uv(Vector2::new(1.0, 0.0));
+
+        // The shared edge from 0 to 1 has a different tangent handedness in both triangles
+        let mut mesh = MeshData::new(
+            vec![
+                vertex(0.0, 0.0),
+                vertex(0.0, 1.0),
+                vertex(1.0, 0.0),
+                mirrored,
+            ],
+            vec![[0, 2, 1], [0, 1, 3]],
+        );
+
+        mesh.compute_tangents();
+
+        assert_eq!(mesh.vertices.len(), 6);
+        assert_eq!(mesh.triangles[0], [0, 2, 1]);
+        assert_eq!(mesh.triangles[1], [4, 5, 3]);
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::mesh::VertexAttributes;
+
+use super::MeshData;
+
+/// The smallest cell of the spatial hash, smaller cells would overflow the cell coordinates of typical positions.
+const MIN_CELL_SIZE: f32 = 1e-6;
+
+impl<V: VertexAttributes + PartialEq> MeshData<V> {
+    /// Merges vertices whose positions, normals, texture coordinates and tangents differ by at most `epsilon` and whose
+    /// other fields are equal, the first of them is kept.
+    ///
+    /// Triangles that collapse are removed, as are vertices no triangle uses.
+    pub fn weld(&mut self, epsilon: f32) {
+        let cell_size = epsilon.max(MIN_CELL_SIZE);
+        let cell =
+            |position: Vector3<f32>| (position / cell_size).map(|value| value.floor() as i64);
+
+        let mut cells = HashMap::<Vector3<i64>, Vec<u32>>::new();
+        let mut remap = Vec::with_capacity(self.vertices.len());
+
+        for (idx, vertex) in self.vertices.iter().enumerate() {
+            let center = cell(vertex.position());
+
+            let welded = neighbour_cells(center)
+                .filter_map(|cell| cells.get(&cell))
+                .flatten()
+                .copied()
+                .find(|&other| is_weldable(vertex, &self.vertices[other as usize], epsilon));
+
+            remap.push(welded.unwrap_or_else(|| {
+                cells.entry(center).or_default().push(idx as u32);
+                idx as u32
+            }));
+        }
+
+        self.triangles = self
+            .triangles
+            .iter()
+            .map(|triangle| triangle.map(|idx| remap[idx as usize]))
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .collect();
+
+        self.remove_unused_vertices();
+    }
+}
+
+/// The cell and all cells touching it.
+fn neighbour_cells(center: Vector3<i64>) -> impl Iterator<Item = Vector3<i64>> {
+    (-1..=1).flat_map(move |x| {
+        (-1..=1).flat_map(move |y| {
+            (-1..=1).map(move |z| {
+                Vector3::new(
+                    center.x.saturating_add(x),
+                    center.y.saturating_add(y),
+                    center.z.saturating_add(z),
+                )
+            })
+        })
+    })
+}
+
+fn is_weldable<V: VertexAttributes + PartialEq>(vertex: &V, other: &V, epsilon: f32) -> bool {
+    fn close<T: InnerSpace<Scalar = f32>>(a: Option<T>, b: Option<T>, epsilon: f32) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) => (a - b).magnitude() <= epsilon,
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    let attributes_close = (vertex.position() - other.position()).magnitude() <= epsilon
+        && close(vertex.normal(), other.normal(), epsilon)
+        && close(vertex.uv(), other.uv(), epsilon)
+        && close(vertex.tangent(), other.tangent(), epsilon);
+
+    if !attributes_close {
+        return false;
+    }
+
+    // Compares all other fields
+    let mut candidate = vertex.clone();
+    candidate.set_position(other.position());
+    if let Some(normal) = other.normal() {
+        candidate.set_normal(normal);
+    }
+    if let Some(uv) = other.uv() {
+        candidate.set_uv(uv);
+    }
+    if let Some(tangent) = other.tangent() {
+        candidate.set_tangent(tangent);
+    }
+
+    candidate == *other
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Vector2, Vector3};
+
+    use crate::mesh::{MeshAttributes, MeshData};
+
+    fn vertex(x: f32, y: f32) -> MeshAttributes {
+        MeshAttributes::new(Vector3::new(x, y, 0.0))
+    }
+
+    #[test]
+    fn zero_epsilon_merges_exact_duplicates() {
+        let mut mesh = MeshData::new(
+            vec![
+                vertex(1.0, 0.0),
+                vertex(2.0, 0.0),
+                vertex(3.0, 0.0),
+                vertex(3.0, 0.0),
+            ],
+            vec![[0, 1, 2], [0, 1, 3]],
+        );
+
+        mesh.weld(0.0);
+
+        assert_eq!(mesh.vertices.len(), 3);
+        assert_eq!(mesh.triangles, [[0, 1, 2], [0, 1, 2]]);
+    }
+
+    #[test]
+    fn zero_epsilon_keeps_extreme_positions() {
+        let mut mesh = MeshData::new(
+            vec![
+                vertex(f32::MAX, 0.0),
+                vertex(f32::MIN, 0.0),
+                vertex(0.0, f32::MAX),
+            ],
+            vec![[0, 1, 2]],
+        );
+
+        mesh.weld(0.0);
+
+        assert_eq!(mesh.vertices.len(), 3);
+    }
+
+    #[test]
+    fn small_epsilon_merges_close_vertices() {
+        let mut mesh = MeshData::new(
+            vec![
+                vertex(0.0, 0.0),
+                vertex(1.0, 0.0),
+                vertex(0.0, 1.0),
+                vertex(1.0005, 0.0),
+                vertex(0.0, 1.01),
+            ],
+            vec![[0, 1, 2], [0, 3, 4]],
+        );
+
+        mesh.weld(0.001);
+
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.triangles, [[0, 1, 2], [0, 1, 3]]);
+    }
+
+    #[test]
+    fn differing_attributes_are_kept() {
+        let mut textured = vertex(1.0, 0.0);
+        textured.uv = Some(Vector2::new(0.5, 0.5));
+
+        let mut mesh = MeshData::new(
+            vec![
+                vertex(0.0, 0.0),
+                vertex(1.0, 0.0),
+                vertex(0.0, 1.0),
+                textured,
+            ],
+            vec![[0, 1, 2], [0, 3, 2]],
+        );
+
+        mesh.weld(0.001);
+
+        assert_eq!(mesh.vertices.len(), 4);
+    }
+
+    #[test]
+    fn collapsed_triangles_are_removed() {
+        let mut mesh = MeshData::new(
+            vec![
+                vertex(0.0, 0.0),
+                vertex(1.0, 0.0),
+                vertex(0.0, 1.0),
+                vertex(5.0, 5.0),
+                vertex(5.0, 5.0001),
+            ],
+            vec![[0, 1, 2], [1, 3, 4]],
+        );
+
+        mesh.weld(0.001);
+
+        assert_eq!(mesh.triangles, [[0, 1, 2]]);
+        assert_eq!(mesh.vertices.len(), 3);
+    }
+}
@@ -1,3 +1,5 @@
+use cgmath::Vector3;
+
 use super::{Vertex, wgpu::VertexBufferLayout};
 
 pub trait MeshInstance: Sized {
@@ -7,4 +9,12 @@ pub trait MeshInstance: Sized {
     const LAYOUT: VertexBufferLayout<'static>;
 
     fn shader_struct_str() -> &'static str;
+
+    /// The world space position of the instance, used to sort transparent instances with a
+    /// [`DepthSorter`](crate::bounds::DepthSorter).
+    ///
+    /// The derive returns the field marked with `#[position]`, either a vector or the translation of a 4x4 matrix.
+    fn position(&self) -> Option<Vector3<f32>> {
+        None
+    }
 }
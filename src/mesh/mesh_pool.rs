@@ -0,0 +1,202 @@
+use std::any::type_name;
+
+use wgpu::{Device, Queue, RenderPass, util::DrawIndexedIndirectArgs};
+
+use crate::{
+    DebugGroupExt,
+    gpu_buffer::{GpuBuffer, IndexBuf, InstanceBuf, VertexBuf},
+};
+
+use super::{MeshInstance, free_list::FreeList, vertex::Vertex};
+
+/// Many meshes sharing a single vertex and index buffer.
+///
+/// Indices are stored as `u32` and are relative to the first vertex of their mesh, see [`MeshHandle`].
+/// Meshes drawn with [`MeshPool::draw_batch`] share the vertex and index buffer bindings.
+pub struct MeshPool<V, const PRIMITIVE_SIZE: usize = 3> {
+    vertices: GpuBuffer<V, VertexBuf>,
+    indices: GpuBuffer<u32, IndexBuf<PRIMITIVE_SIZE>>,
+    free_vertices: FreeList,
+    free_indices: FreeList,
+    /// `None` if the pool uses the default label, see [`MeshPool::label`]
+    label: Option<String>,
+}
+
+/// A mesh inside of a [`MeshPool`].
+///
+/// Only valid for the pool that created it. Handles can't be cloned, so a mesh can't be freed twice.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MeshHandle {
+    base_vertex: u32,
+    vertex_count: u32,
+    first_index: u32,
+    index_count: u32,
+}
+
+impl MeshHandle {
+    pub fn base_vertex(&self) -> u32 {
+        self.base_vertex
+    }
+
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_count
+    }
+
+    pub fn first_index(&self) -> u32 {
+        self.first_index
+    }
+
+    pub fn index_count(&self) -> u32 {
+        self.index_count
+    }
+
+    /// The indirect arguments that draw the mesh `instance_count` times.
+    pub fn indirect_args(&self, instance_count: u32) -> DrawIndexedIndirectArgs {
+        DrawIndexedIndirectArgs {
+            index_count: self.index_count,
+            instance_count,
+            first_index: self.first_index,
+            base_vertex: self.base_vertex as i32,
+            first_instance: 0,
+        }
+    }
+}
+
+impl<V: Vertex, const C: usize> MeshPool<V, C> {
+    /// Creates a pool with space for `vertex_capacity` vertices and `index_capacity` indices. The pool grows when it is full.
+    ///
+    /// Empty buffers can't be sliced, so both capacities are at least 1.
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        vertex_capacity: u32,
+        index_capacity: u32,
+        label: Option<&str>,
+    ) -> Self {
+        let (vertex_capacity, index_capacity) = (vertex_capacity.max(1), index_capacity.max(1));
+
+        let mut vertices = GpuBuffer::vertices(device, [], label);
+        vertices.resize(device, queue, vertex_capacity);
+
+        let mut indices = GpuBuffer::indices_u32(device, &[], label);
+        indices.resize_u32(device, queue, index_capacity);
+
+        Self {
+            vertices,
+            indices,
+            free_vertices: FreeList::new(vertex_capacity),
+            free_indices: FreeList::new(index_capacity),
+            label: label.map(String::from),
+        }
+    }
+
+    /// Adds a mesh to the pool. `indices` are relative to the first vertex of `vertices`.
+    pub fn add(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        vertices: Vec<V>,
+        indices: Vec<[u32; C]>,
+    ) -> MeshHandle {
+        let vertex_count = vertices.len() as u32;
+        let indices = indices.into_flattened();
+        let index_count = indices.len() as u32;
+
+        let base_vertex = match self.free_vertices.allocate(vertex_count) {
+            Some(start) => start,
+            None => {
+                let capacity = self
+                    .free_vertices
+                    .required_capacity(vertex_count)
+                    .max(self.free_vertices.capacity() * 2);
+
+                self.vertices.resize(device, queue, capacity);
+                self.free_vertices.grow(capacity);
+                self.free_vertices
+                    .allocate(vertex_count)
+                    .expect("the grown pool fits the vertices")
+            }
+        };
+
+        let first_index = match self.free_indices.allocate(index_count) {
+            Some(start) => start,
+            None => {
+                let capacity = self
+                    .free_indices
+                    .required_capacity(index_count)
+                    .max(self.free_indices.capacity() * 2);
+
+                self.indices.resize_u32(device, queue, capacity);
+                self.free_indices.grow(capacity);
+                self.free_indices
+                    .allocate(index_count)
+                    .expect("the grown pool fits the indices")
+            }
+        };
+
+        self.vertices.update_range(queue, base_vertex, vertices);
+        self.indices.update_range_u32(queue, first_index, &indices);
+
+        MeshHandle {
+            base_vertex,
+            vertex_count,
+            first_index,
+            index_count,
+        }
+    }
+}
+
+impl<V, const C: usize> MeshPool<V, C> {
+    /// The debug label of the pool, defaults to the type name of `V`.
+    ///
+    /// Batches drawn from the pool are wrapped in a debug group with this label.
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(type_name::<V>())
+    }
+
+    /// Makes the space of `mesh` available for new meshes.
+    pub fn free(&mut self, mesh: MeshHandle) {
+        self.free_vertices
+            .free(mesh.base_vertex..mesh.base_vertex + mesh.vertex_count);
+        self.free_indices
+            .free(mesh.first_index..mesh.first_index + mesh.index_count);
+    }
+
+    /// Binds the shared vertex and index buffers.
+    pub fn set_buffers<'a>(&'a self, pass: &mut RenderPass<'a>) {
+        pass.set_index_buffer(self.indices.slice(), self.indices.index_format());
+        pass.set_vertex_buffer(0, self.vertices.slice());
+    }
+
+    pub fn draw<'a, I>(
+        &'a self,
+        mesh: &MeshHandle,
+        instances: &'a GpuBuffer<I, InstanceBuf>,
+        pass: &mut RenderPass<'a>,
+    ) where
+        I: MeshInstance<Vertex = V>,
+    {
+        self.draw_batch([(mesh, instances)], pass);
+    }
+
+    /// Draws several meshes of the pool, the vertex and index buffers are only bound once.
+    pub fn draw_batch<'a, 'm, I>(
+        &'a self,
+        draws: impl IntoIterator<Item = (&'m MeshHandle, &'a GpuBuffer<I, InstanceBuf>)>,
+        pass: &mut RenderPass<'a>,
+    ) where
+        I: MeshInstance<Vertex = V> + 'a,
+    {
+        let mut pass = pass.debug_group(self.label());
+        self.set_buffers(&mut pass);
+
+        for (mesh, instances) in draws {
+            pass.set_vertex_buffer(1, instances.slice());
+            pass.draw_indexed(
+                mesh.first_index..mesh.first_index + mesh.index_count,
+                mesh.base_vertex as i32,
+                0..instances.count(),
+            );
+        }
+    }
+}
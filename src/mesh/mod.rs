@@ -1,13 +1,32 @@
+mod free_list;
 mod mesh;
+mod mesh_attributes;
+mod mesh_data;
+mod mesh_pool;
+mod morph;
 mod vertex;
 mod mesh_instance;
+mod submesh;
+mod topology;
+mod vertex_attributes;
+pub mod shapes;
 
 pub use mesh::Mesh;
+pub use mesh_attributes::MeshAttributes;
+pub use mesh_data::MeshData;
+pub use mesh_pool::{MeshHandle, MeshPool};
+pub use morph::{MAX_MORPH_TARGETS, MorphDelta, MorphTarget, MorphTargets, MorphWeights};
+pub use submesh::Submesh;
 
 pub use sifu_render_derive::Vertex;
 pub use vertex::Vertex;
 pub use sifu_render_derive::MeshInstance;
 pub use mesh_instance::MeshInstance;
+pub use sifu_render_derive::FromMeshAttributes;
+pub use mesh_attributes::FromMeshAttributes;
+pub use sifu_render_derive::VertexAttributes;
+pub use vertex_attributes::VertexAttributes;
+pub use topology::*;
 
 pub mod wgpu {
     pub use wgpu::{VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode};
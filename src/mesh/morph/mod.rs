@@ -0,0 +1,9 @@
+mod morph_delta;
+mod morph_target;
+mod morph_targets;
+mod morph_weights;
+
+pub use morph_delta::MorphDelta;
+pub use morph_target::MorphTarget;
+pub use morph_targets::MorphTargets;
+pub use morph_weights::{MAX_MORPH_TARGETS, MorphWeights};
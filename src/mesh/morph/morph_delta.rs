@@ -0,0 +1,26 @@
+use cgmath::{Vector3, Zero};
+
+use crate::{self as sifu_render, Uniform};
+
+/// The offset of a single vertex in a [`MorphTarget`](super::MorphTarget).
+///
+/// [`MorphTargets`](super::MorphTargets) are bound as `array<MorphDelta>`, the shader prelude has morphing helpers.
+#[derive(Uniform, Debug, Clone, Copy, PartialEq)]
+pub struct MorphDelta {
+    #[raw(f32; 3)]
+    pub position: Vector3<f32>,
+    #[raw(f32; 3)]
+    pub normal: Vector3<f32>,
+}
+
+impl MorphDelta {
+    pub fn new(position: Vector3<f32>, normal: Vector3<f32>) -> Self {
+        Self { position, normal }
+    }
+}
+
+impl Default for MorphDelta {
+    fn default() -> Self {
+        Self::new(Vector3::zero(), Vector3::zero())
+    }
+}
@@ -0,0 +1,30 @@
+use cgmath::{Vector3, Zero};
+
+use super::MorphDelta;
+
+/// A blend shape, the offsets of every vertex of a mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MorphTarget {
+    pub name: String,
+    /// One per vertex of the mesh
+    pub deltas: Vec<MorphDelta>,
+}
+
+impl MorphTarget {
+    pub fn new(name: impl Into<String>, deltas: Vec<MorphDelta>) -> Self {
+        Self {
+            name: name.into(),
+            deltas,
+        }
+    }
+
+    /// A target that only moves the vertices, the normals stay the same.
+    pub fn from_positions(name: impl Into<String>, positions: &[Vector3<f32>]) -> Self {
+        let deltas = positions
+            .iter()
+            .map(|&position| MorphDelta::new(position, Vector3::zero()))
+            .collect();
+
+        Self::new(name, deltas)
+    }
+}
@@ -0,0 +1,127 @@
+use wgpu::Device;
+
+use crate::StorageBuffer;
+
+use super::{MAX_MORPH_TARGETS, MorphDelta, MorphTarget, MorphWeights};
+
+/// The [`MorphTarget`]s of a mesh on the GPU, see [`Mesh::with_morph_targets`](crate::mesh::Mesh::with_morph_targets).
+///
+/// The deltas of all targets are stored one target after the other, the delta of a vertex in target `t` is at
+/// `t * vertex_count + vertex_index`, where `vertex_index` is counted from the first vertex of the mesh.
+pub struct MorphTargets {
+    deltas: StorageBuffer<MorphDelta>,
+    names: Vec<String>,
+    vertex_count: u32,
+}
+
+impl MorphTargets {
+    /// The debug label defaults to the type name of [`MorphDelta`].
+    ///
+    /// # Panics
+    /// If a target doesn't have a delta for each of the `vertex_count` vertices or there are more than
+    /// [`MAX_MORPH_TARGETS`] targets.
+    pub fn new(
+        device: &Device,
+        vertex_count: u32,
+        targets: &[MorphTarget],
+        label: Option<&str>,
+    ) -> Self {
+        assert!(
+            targets.len() <= MAX_MORPH_TARGETS,
+            "{} morph targets exceed the maximum of {MAX_MORPH_TARGETS}",
+            targets.len(),
+        );
+
+        for target in targets {
+            assert_eq!(
+                target.deltas.len(),
+                vertex_count as usize,
+                "morph target {} needs a delta for every vertex",
+                target.name,
+            );
+        }
+
+        let deltas = targets
+            .iter()
+            .flat_map(|target| target.deltas.iter().copied());
+
+        Self {
+            deltas: StorageBuffer::storage(device, deltas, label),
+            names: targets.iter().map(|target| target.name.clone()).collect(),
+            vertex_count,
+        }
+    }
+
+    /// Bind it as `array<MorphDelta>`.
+    pub fn deltas(&self) -> &StorageBuffer<MorphDelta> {
+        &self.deltas
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The index of the first target called `name`, used to set its weight.
+    pub fn target(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|target| target == name)
+    }
+
+    pub fn target_count(&self) -> u32 {
+        self.names.len() as u32
+    }
+
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_count
+    }
+
+    /// Weights for these targets, all 0.
+    pub fn weights(&self) -> MorphWeights {
+        MorphWeights::new(self.target_count(), self.vertex_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    use wgpu::Device;
+
+    use super::MorphTargets;
+    use crate::mesh::{MorphDelta, MorphTarget};
+
+    /// `None` if there is no adapter, e.g. on CI machines without a GPU or software renderer.
+    fn device() -> Option<Device> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&Default::default())).ok()?;
+        let (device, _) = pollster::block_on(adapter.request_device(&Default::default())).ok()?;
+
+        Some(device)
+    }
+
+    #[test]
+    fn mismatched_delta_counts_panic() {
+        let Some(device) = device() else {
+            return;
+        };
+
+        let targets = [
+            MorphTarget::new("smile", vec![MorphDelta::default(); 3]),
+            MorphTarget::new("frown", vec![MorphDelta::default(); 2]),
+        ];
+
+        let panic = catch_unwind(AssertUnwindSafe(|| {
+            MorphTargets::new(&device, 3, &targets, None);
+        }))
+        .expect_err("MorphTargets::new accepted a target without a delta for every vertex");
+
+        let message = panic
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .unwrap_or_default();
+        assert!(message.contains("morph target frown needs a delta for every vertex"));
+
+        let targets = MorphTargets::new(&device, 3, &targets[..1], None);
+        assert_eq!(targets.target_count(), 1);
+        assert_eq!(targets.target("smile"), Some(0));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{self as sifu_render, Uniform};
+
+/// The number of weights of [`MorphWeights`].
+pub const MAX_MORPH_TARGETS: usize = 16;
+
+/// The weights of the [`MorphTargets`](super::MorphTargets) of an instance, update them every frame to animate it.
+///
+/// Use a [`UniformArrayBuffer`](crate::UniformArrayBuffer) with a dynamic offset or a
+/// [`StorageBuffer`](crate::StorageBuffer) indexed by `instance_index` for multiple instances.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MorphWeights {
+    weights: [f32; MAX_MORPH_TARGETS],
+    target_count: u32,
+    vertex_count: u32,
+}
+
+impl MorphWeights {
+    /// All weights start at 0, prefer [`MorphTargets::weights`](super::MorphTargets::weights).
+    ///
+    /// # Panics
+    /// If `target_count` exceeds [`MAX_MORPH_TARGETS`].
+    pub fn new(target_count: u32, vertex_count: u32) -> Self {
+        assert!(
+            target_count as usize <= MAX_MORPH_TARGETS,
+            "{target_count} morph targets exceed the maximum of {MAX_MORPH_TARGETS}",
+        );
+
+        Self {
+            weights: [0.0; MAX_MORPH_TARGETS],
+            target_count,
+            vertex_count,
+        }
+    }
+
+    /// The weight of every target.
+    pub fn weights(&self) -> &[f32] {
+        &self.weights[..self.target_count as usize]
+    }
+
+    /// # Panics
+    /// If there is no target at `target`.
+    pub fn weight(&self, target: usize) -> f32 {
+        self.weights()[target]
+    }
+
+    /// # Panics
+    /// If there is no target at `target`.
+    pub fn set_weight(&mut self, target: usize, weight: f32) {
+        let target_count = self.target_count as usize;
+        self.weights[..target_count][target] = weight;
+    }
+
+    /// Sets the weights of the first `weights.len()` targets.
+    ///
+    /// # Panics
+    /// If there are more weights than targets.
+    pub fn set_weights(&mut self, weights: &[f32]) {
+        let target_count = self.target_count as usize;
+        self.weights[..target_count][..weights.len()].copy_from_slice(weights);
+    }
+
+    pub fn reset(&mut self) {
+        self.weights = [0.0; MAX_MORPH_TARGETS];
+    }
+
+    pub fn target_count(&self) -> u32 {
+        self.target_count
+    }
+
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_count
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Zeroable, bytemuck::Pod)]
+#[bytemuck(crate = "sifu_render::bytemuck")]
+pub struct MorphWeightsRaw {
+    weights: [[f32; 4]; MAX_MORPH_TARGETS / 4],
+    target_count: u32,
+    vertex_count: u32,
+    padding: [u32; 2],
+}
+
+impl From<MorphWeights> for MorphWeightsRaw {
+    fn from(value: MorphWeights) -> Self {
+        Self {
+            weights: sifu_render::bytemuck::cast(value.weights),
+            target_count: value.target_count,
+            vertex_count: value.vertex_count,
+            padding: [0; 2],
+        }
+    }
+}
+
+impl Uniform for MorphWeights {
+    type Raw = MorphWeightsRaw;
+
+    fn shader_struct_str() -> &'static str {
+        "struct MorphWeights {
+            weights: array<vec4<f32>, 4>,
+            target_count: u32,
+            vertex_count: u32,
+        }"
+    }
+
+    fn shader_struct_name() -> &'static str {
+        "MorphWeights"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_MORPH_TARGETS, MorphWeights, MorphWeightsRaw};
+
+    #[test]
+    fn raw_layout_matches_the_shader_struct() {
+        // array<vec4<f32>, 4> takes 64 bytes, the two u32 are padded to the 16 byte alignment of the struct
+        assert_eq!(size_of::<MorphWeightsRaw>(), 80);
+        assert_eq!(MAX_MORPH_TARGETS % 4, 0);
+    }
+
+    #[test]
+    fn weights_are_packed_in_target_order() {
+        let mut weights = MorphWeights::new(MAX_MORPH_TARGETS as u32, 10);
+
+        for target in 0..MAX_MORPH_TARGETS {
+            weights.set_weight(target, target as f32);
+        }
+
+        let raw = MorphWeightsRaw::from(weights);
+
+        // `morph_weight` in the prelude reads `weights[t / 4][t % 4]`
+        for target in 0..MAX_MORPH_TARGETS {
+            assert_eq!(raw.weights[target / 4][target % 4], target as f32);
+        }
+
+        assert_eq!(raw.target_count, MAX_MORPH_TARGETS as u32);
+        assert_eq!(raw.vertex_count, 10);
+    }
+
+    #[test]
+    fn set_weights_keeps_later_targets() {
+        let mut weights = MorphWeights::new(3, 10);
+
+        weights.set_weight(2, 0.5);
+        weights.set_weights(&[0.25, 0.75]);
+
+        assert_eq!(weights.weights(), [0.25, 0.75, 0.5]);
+
+        weights.reset();
+        assert_eq!(weights.weights(), [0.0; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_weight_past_the_target_count() {
+        MorphWeights::new(3, 10).set_weight(3, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_more_weights_than_targets() {
+        MorphWeights::new(3, 10).set_weights(&[1.0; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceed the maximum")]
+    fn too_many_targets() {
+        MorphWeights::new(MAX_MORPH_TARGETS as u32 + 1, 10);
+    }
+}
@@ -0,0 +1,113 @@
+use std::f32::consts::FRAC_PI_2;
+
+use cgmath::{InnerSpace, Vector2};
+
+use super::{ProfilePoint, Shape, ShapeMesh};
+
+/// A cylinder with hemispheres at both ends around the y axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule {
+    pub radius: f32,
+    /// The total height including the hemispheres, at least `2 * radius`
+    pub height: f32,
+    /// The number of segments around the y axis
+    pub segments: u32,
+    /// The number of rows of each hemisphere
+    pub rings: u32,
+    /// The number of rows of the cylinder
+    pub height_segments: u32,
+}
+
+impl Capsule {
+    pub fn new(radius: f32, height: f32) -> Self {
+        Self {
+            radius,
+            height,
+            segments: 32,
+            rings: 8,
+            height_segments: 1,
+        }
+    }
+
+    pub fn with_segments(self, segments: u32) -> Self {
+        Self {
+            segments: segments.max(3),
+            ..self
+        }
+    }
+
+    pub fn with_rings(self, rings: u32) -> Self {
+        Self {
+            rings: rings.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_height_segments(self, height_segments: u32) -> Self {
+        Self {
+            height_segments: height_segments.max(1),
+            ..self
+        }
+    }
+}
+
+impl Shape for Capsule {
+    fn generate(&self) -> ShapeMesh {
+        let capsule = self
+            .with_segments(self.segments)
+            .with_rings(self.rings)
+            .with_height_segments(self.height_segments);
+
+        let mut mesh = ShapeMesh::new();
+
+        let half_cylinder = (capsule.height / 2.0 - capsule.radius).max(0.0);
+
+        let hemisphere = |ring: u32, center: f32, start_angle: f32| {
+            let angle = start_angle + ring as f32 / capsule.rings as f32 * FRAC_PI_2;
+            let (sin, cos) = angle.sin_cos();
+
+            (
+                capsule.radius * sin,
+                center + capsule.radius * cos,
+                Vector2::new(sin, cos),
+            )
+        };
+
+        let points = (0..=capsule.rings)
+            .map(|ring| hemisphere(ring, half_cylinder, 0.0))
+            .chain((1..capsule.height_segments).map(|row| {
+                let y = half_cylinder
+                    - row as f32 / capsule.height_segments as f32 * 2.0 * half_cylinder;
+                (capsule.radius, y, Vector2::new(1.0, 0.0))
+            }))
+            .chain((0..=capsule.rings).map(|ring| hemisphere(ring, -half_cylinder, FRAC_PI_2)))
+            .collect::<Vec<_>>();
+
+        // The texture coordinates follow the length of the profile
+        let mut length = 0.0;
+        let mut lengths = Vec::with_capacity(points.len());
+
+        for (idx, (radius, y, _)) in points.iter().enumerate() {
+            if let Some((prev_radius, prev_y, _)) = idx.checked_sub(1).map(|prev| points[prev]) {
+                length += Vector2::new(radius - prev_radius, y - prev_y).magnitude();
+            }
+
+            lengths.push(length);
+        }
+
+        let profile = points
+            .iter()
+            .zip(lengths)
+            .map(|(&(radius, y, normal), point_length)| ProfilePoint {
+                radius,
+                y,
+                normal,
+                v: point_length / length,
+            })
+            .collect::<Vec<_>>();
+
+        mesh.push_revolution(capsule.segments, &profile);
+
+        mesh.with_tangents()
+    }
+}
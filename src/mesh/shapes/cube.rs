@@ -0,0 +1,57 @@
+use cgmath::{ElementWise, Vector2, Vector3};
+
+use super::{Shape, ShapeMesh};
+
+/// An axis aligned box, every face has its own vertices and the full texture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cube {
+    pub size: Vector3<f32>,
+    /// The number of rows and columns of every face
+    pub subdivisions: u32,
+}
+
+impl Cube {
+    pub fn new(size: Vector3<f32>) -> Self {
+        Self {
+            size,
+            subdivisions: 1,
+        }
+    }
+
+    pub fn with_subdivisions(self, subdivisions: u32) -> Self {
+        Self {
+            subdivisions: subdivisions.max(1),
+            ..self
+        }
+    }
+}
+
+impl Shape for Cube {
+    fn generate(&self) -> ShapeMesh {
+        let cube = self.with_subdivisions(self.subdivisions);
+
+        let mut mesh = ShapeMesh::new();
+        let half_size = cube.size / 2.0;
+
+        // The normal, right and up direction of every face seen from outside
+        let faces = [
+            (Vector3::unit_x(), -Vector3::unit_z(), Vector3::unit_y()),
+            (-Vector3::unit_x(), Vector3::unit_z(), Vector3::unit_y()),
+            (Vector3::unit_y(), Vector3::unit_x(), -Vector3::unit_z()),
+            (-Vector3::unit_y(), Vector3::unit_x(), Vector3::unit_z()),
+            (Vector3::unit_z(), Vector3::unit_x(), Vector3::unit_y()),
+            (-Vector3::unit_z(), -Vector3::unit_x(), Vector3::unit_y()),
+        ];
+
+        for (normal, right, up) in faces {
+            mesh.push_grid(
+                normal.mul_element_wise(half_size),
+                right.mul_element_wise(half_size),
+                up.mul_element_wise(half_size),
+                Vector2::new(cube.subdivisions, cube.subdivisions),
+            );
+        }
+
+        mesh.with_tangents()
+    }
+}
@@ -0,0 +1,175 @@
+use cgmath::{InnerSpace, Vector2, Vector3};
+
+use super::{ProfilePoint, Shape, ShapeMesh};
+
+/// A cylinder around the y axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cylinder {
+    pub radius: f32,
+    pub height: f32,
+    /// The number of segments around the y axis
+    pub segments: u32,
+    /// The number of rows along the y axis
+    pub height_segments: u32,
+    /// Whether the top and bottom are closed
+    pub caps: bool,
+}
+
+/// A cone around the y axis with the tip at the top.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cone {
+    /// The radius of the base
+    pub radius: f32,
+    pub height: f32,
+    /// The number of segments around the y axis
+    pub segments: u32,
+    /// The number of rows along the y axis
+    pub height_segments: u32,
+    /// Whether the base is closed
+    pub cap: bool,
+}
+
+impl Cylinder {
+    pub fn new(radius: f32, height: f32) -> Self {
+        Self {
+            radius,
+            height,
+            segments: 32,
+            height_segments: 1,
+            caps: true,
+        }
+    }
+
+    pub fn with_segments(self, segments: u32) -> Self {
+        Self {
+            segments: segments.max(3),
+            ..self
+        }
+    }
+
+    pub fn with_height_segments(self, height_segments: u32) -> Self {
+        Self {
+            height_segments: height_segments.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_caps(self, caps: bool) -> Self {
+        Self { caps, ..self }
+    }
+}
+
+impl Cone {
+    pub fn new(radius: f32, height: f32) -> Self {
+        Self {
+            radius,
+            height,
+            segments: 32,
+            height_segments: 1,
+            cap: true,
+        }
+    }
+
+    pub fn with_segments(self, segments: u32) -> Self {
+        Self {
+            segments: segments.max(3),
+            ..self
+        }
+    }
+
+    pub fn with_height_segments(self, height_segments: u32) -> Self {
+        Self {
+            height_segments: height_segments.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_cap(self, cap: bool) -> Self {
+        Self { cap, ..self }
+    }
+}
+
+impl Shape for Cylinder {
+    fn generate(&self) -> ShapeMesh {
+        let cylinder = self
+            .with_segments(self.segments)
+            .with_height_segments(self.height_segments);
+
+        let mut mesh = ShapeMesh::new();
+        let half_height = cylinder.height / 2.0;
+
+        let profile = (0..=cylinder.height_segments)
+            .map(|row| {
+                let v = row as f32 / cylinder.height_segments as f32;
+
+                ProfilePoint {
+                    radius: cylinder.radius,
+                    y: half_height - v * cylinder.height,
+                    normal: Vector2::new(1.0, 0.0),
+                    v,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        mesh.push_revolution(cylinder.segments, &profile);
+
+        if cylinder.caps {
+            mesh.push_disk(
+                Vector3::unit_y() * half_height,
+                Vector3::unit_x(),
+                -Vector3::unit_z(),
+                cylinder.radius,
+                cylinder.segments,
+            );
+            mesh.push_disk(
+                -Vector3::unit_y() * half_height,
+                Vector3::unit_x(),
+                Vector3::unit_z(),
+                cylinder.radius,
+                cylinder.segments,
+            );
+        }
+
+        mesh.with_tangents()
+    }
+}
+
+impl Shape for Cone {
+    fn generate(&self) -> ShapeMesh {
+        let cone = self
+            .with_segments(self.segments)
+            .with_height_segments(self.height_segments);
+
+        let mut mesh = ShapeMesh::new();
+        let half_height = cone.height / 2.0;
+
+        let normal = Vector2::new(cone.height, cone.radius).normalize();
+
+        let profile = (0..=cone.height_segments)
+            .map(|row| {
+                let v = row as f32 / cone.height_segments as f32;
+
+                ProfilePoint {
+                    radius: cone.radius * v,
+                    y: half_height - v * cone.height,
+                    normal,
+                    v,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        mesh.push_revolution(cone.segments, &profile);
+
+        if cone.cap {
+            mesh.push_disk(
+                -Vector3::unit_y() * half_height,
+                Vector3::unit_x(),
+                Vector3::unit_z(),
+                cone.radius,
+                cone.segments,
+            );
+        }
+
+        mesh.with_tangents()
+    }
+}
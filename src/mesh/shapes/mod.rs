@@ -0,0 +1,17 @@
+mod capsule;
+mod cube;
+mod cylinder;
+mod plane;
+mod shape;
+mod sphere;
+mod torus;
+
+pub use capsule::Capsule;
+pub use cube::Cube;
+pub use cylinder::{Cone, Cylinder};
+pub use plane::Plane;
+pub use shape::{Shape, ShapeMesh};
+pub use sphere::{Icosphere, UvSphere};
+pub use torus::Torus;
+
+use shape::ProfilePoint;
@@ -0,0 +1,45 @@
+use cgmath::{Vector2, Vector3};
+
+use super::{Shape, ShapeMesh};
+
+/// A grid in the xz plane facing `+y`, the top of the texture points towards `-z`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    /// The size along x and z
+    pub size: Vector2<f32>,
+    /// The number of columns along x and rows along z
+    pub subdivisions: Vector2<u32>,
+}
+
+impl Plane {
+    pub fn new(size: Vector2<f32>) -> Self {
+        Self {
+            size,
+            subdivisions: Vector2::new(1, 1),
+        }
+    }
+
+    pub fn with_subdivisions(self, subdivisions: Vector2<u32>) -> Self {
+        Self {
+            subdivisions: subdivisions.map(|count| count.max(1)),
+            ..self
+        }
+    }
+}
+
+impl Shape for Plane {
+    fn generate(&self) -> ShapeMesh {
+        let plane = self.with_subdivisions(self.subdivisions);
+
+        let mut mesh = ShapeMesh::new();
+
+        mesh.push_grid(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::unit_x() * plane.size.x / 2.0,
+            -Vector3::unit_z() * plane.size.y / 2.0,
+            plane.subdivisions,
+        );
+
+        mesh.with_tangents()
+    }
+}
@@ -0,0 +1,309 @@
+use std::f32::consts::TAU;
+
+use cgmath::{InnerSpace, Vector2, Vector3};
+use wgpu::Device;
+
+use crate::{
+    bounds::MeshBounds,
+    mesh::{FromMeshAttributes, Mesh, MeshAttributes, MeshData, Vertex},
+};
+
+/// A procedurally generated shape.
+///
+/// Shapes are centered at the origin with `+y` up, front faces are counter-clockwise and the texture coordinates start
+/// in the top left corner. Counts below the minimum of their builder, e.g.
+/// [`UvSphere::with_segments`](super::UvSphere::with_segments), are clamped.
+pub trait Shape {
+    fn generate(&self) -> ShapeMesh;
+
+    fn to_mesh<V: Vertex + FromMeshAttributes>(
+        &self,
+        device: &Device,
+        label: Option<&str>,
+    ) -> Mesh<V> {
+        self.generate().to_mesh(device, label)
+    }
+
+    /// The generated shape as [`MeshData`] for further processing.
+    fn to_data<V: FromMeshAttributes>(&self) -> MeshData<V> {
+        let mesh = self.generate();
+
+        MeshData::from_attributes(&mesh.vertices, mesh.triangles)
+    }
+}
+
+/// The vertices and triangles of a [`Shape`], every vertex has a position, normal and texture coordinates.
+///
+/// With the `tangents` feature the vertices also have MikkTSpace tangents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeMesh {
+    pub vertices: Vec<MeshAttributes>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+/// A point of the profile of a surface of revolution.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ProfilePoint {
+    /// The distance from the y axis
+    pub radius: f32,
+    pub y: f32,
+    /// The radial and y component of the normal
+    pub normal: Vector2<f32>,
+    pub v: f32,
+}
+
+impl ShapeMesh {
+    pub(super) fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            triangles: Vec::new(),
+        }
+    }
+
+    pub(super) fn push_vertex(
+        &mut self,
+        position: Vector3<f32>,
+        normal: Vector3<f32>,
+        uv: Vector2<f32>,
+    ) -> u32 {
+        self.vertices.push(MeshAttributes {
+            position,
+            normal: Some(normal),
+            uv: Some(uv),
+            tangent: None,
+            joints: None,
+            weights: None,
+        });
+
+        self.vertices.len() as u32 - 1
+    }
+
+    /// Pushes the triangle facing the same way as its vertex normals, degenerate triangles are skipped.
+    pub(super) fn push_triangle(&mut self, [a, b, c]: [u32; 3]) {
+        let [va, vb, vc] = [a, b, c].map(|idx| self.vertices[idx as usize]);
+
+        let face_normal = (vb.position - va.position).cross(vc.position - va.position);
+
+        if face_normal.magnitude2() <= f32::EPSILON * f32::EPSILON {
+            return;
+        }
+
+        let normal = va.normal_or_zero() + vb.normal_or_zero() + vc.normal_or_zero();
+
+        match face_normal.dot(normal) < 0.0 {
+            true => self.triangles.push([a, c, b]),
+            false => self.triangles.push([a, b, c]),
+        }
+    }
+
+    /// Pushes a rectangle at `center`, `right` and `up` span half of it.
+    pub(super) fn push_grid(
+        &mut self,
+        center: Vector3<f32>,
+        right: Vector3<f32>,
+        up: Vector3<f32>,
+        subdivisions: Vector2<u32>,
+    ) {
+        let normal = right.cross(up).normalize();
+        let start = self.vertices.len() as u32;
+
+        for row in 0..=subdivisions.y {
+            for column in 0..=subdivisions.x {
+                let uv = Vector2::new(
+                    column as f32 / subdivisions.x as f32,
+                    row as f32 / subdivisions.y as f32,
+                );
+
+                let position = center + right * (uv.x * 2.0 - 1.0) + up * (1.0 - uv.y * 2.0);
+                self.push_vertex(position, normal, uv);
+            }
+        }
+
+        self.push_quads(start, subdivisions);
+    }
+
+    /// Pushes the surface created by rotating `profile` around the y axis.
+    ///
+    /// `u` goes around the axis starting at `+z` towards `+x`, the first and last column share the position but not the
+    /// texture coordinates.
+    pub(super) fn push_revolution(&mut self, segments: u32, profile: &[ProfilePoint]) {
+        let start = self.vertices.len() as u32;
+
+        for point in profile {
+            for column in 0..=segments {
+                let u = column as f32 / segments as f32;
+                let (sin, cos) = (u * TAU).sin_cos();
+                let direction = Vector3::new(sin, 0.0, cos);
+
+                let position = direction * point.radius + Vector3::unit_y() * point.y;
+                let normal = direction * point.normal.x + Vector3::unit_y() * point.normal.y;
+
+                self.push_vertex(position, normal.normalize(), Vector2::new(u, point.v));
+            }
+        }
+
+        self.push_quads(start, Vector2::new(segments, profile.len() as u32 - 1));
+    }
+
+    /// Pushes a disk at `center`, the texture coordinates are mapped like on a rectangle spanned by `right` and `up`.
+    pub(super) fn push_disk(
+        &mut self,
+        center: Vector3<f32>,
+        right: Vector3<f32>,
+        up: Vector3<f32>,
+        radius: f32,
+        segments: u32,
+    ) {
+        let normal = right.cross(up).normalize();
+        let center_idx = self.push_vertex(center, normal, Vector2::new(0.5, 0.5));
+
+        for segment in 0..segments {
+            let (sin, cos) = (segment as f32 / segments as f32 * TAU).sin_cos();
+            let direction = right * cos + up * sin;
+
+            let uv = Vector2::new(0.5 + 0.5 * cos, 0.5 - 0.5 * sin);
+            self.push_vertex(center + direction * radius, normal, uv);
+        }
+
+        for segment in 0..segments {
+            let next = (segment + 1) % segments;
+            self.push_triangle([center_idx, center_idx + 1 + segment, center_idx + 1 + next]);
+        }
+    }
+
+    /// Connects a grid of `(size.x + 1) * (size.y + 1)` vertices starting at `start`, row by row.
+    fn push_quads(&mut self, start: u32, size: Vector2<u32>) {
+        let row_len = size.x + 1;
+
+        for row in 0..size.y {
+            for column in 0..size.x {
+                let top_left = start + row * row_len + column;
+                let bottom_left = top_left + row_len;
+
+                self.push_triangle([top_left, bottom_left, top_left + 1]);
+                self.push_triangle([top_left + 1, bottom_left, bottom_left + 1]);
+            }
+        }
+    }
+
+    /// Computes MikkTSpace tangents with [`MeshData::compute_tangents`].
+    #[cfg(feature = "tangents")]
+    pub(super) fn with_tangents(self) -> Self {
+        let mut data = MeshData::new(self.vertices, self.triangles);
+        data.compute_tangents();
+
+        Self {
+            vertices: data.vertices,
+            triangles: data.triangles,
+        }
+    }
+
+    /// Tangents need the `tangents` feature, the vertices have none without it.
+    #[cfg(not(feature = "tangents"))]
+    pub(super) fn with_tangents(self) -> Self {
+        self
+    }
+
+    pub fn to_vertices<V: FromMeshAttributes>(&self) -> Vec<V> {
+        self.vertices.iter().map(V::from_mesh_attributes).collect()
+    }
+
+    pub fn to_mesh<V: Vertex + FromMeshAttributes>(
+        &self,
+        device: &Device,
+        label: Option<&str>,
+    ) -> Mesh<V> {
+        let mesh = Mesh::new(
+            device,
+            self.to_vertices::<V>(),
+            self.triangles.iter().copied(),
+            label,
+        );
+
+        match self.bounds() {
+            Some(bounds) => mesh.with_bounds(bounds),
+            None => mesh,
+        }
+    }
+
+    /// The bounds of all vertices, `None` if there are none.
+    pub fn bounds(&self) -> Option<MeshBounds> {
+        MeshBounds::from_attributes(&self.vertices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Vector2, Vector3};
+
+    use super::ShapeMesh;
+    use crate::mesh::shapes::{Capsule, Cone, Cube, Cylinder, Plane, Shape, Torus, UvSphere};
+
+    fn zero_count_shapes() -> Vec<ShapeMesh> {
+        vec![
+            UvSphere {
+                segments: 0,
+                rings: 0,
+                ..UvSphere::new(1.0)
+            }
+            .generate(),
+            Capsule {
+                segments: 0,
+                rings: 0,
+                height_segments: 0,
+                ..Capsule::new(0.5, 2.0)
+            }
+            .generate(),
+            Cylinder {
+                segments: 0,
+                height_segments: 0,
+                ..Cylinder::new(1.0, 1.0)
+            }
+            .generate(),
+            Cone {
+                segments: 0,
+                height_segments: 0,
+                ..Cone::new(1.0, 1.0)
+            }
+            .generate(),
+            Cube {
+                subdivisions: 0,
+                ..Cube::new(Vector3::new(1.0, 1.0, 1.0))
+            }
+            .generate(),
+            Plane {
+                subdivisions: Vector2::new(0, 0),
+                ..Plane::new(Vector2::new(1.0, 1.0))
+            }
+            .generate(),
+            Torus {
+                major_segments: 0,
+                minor_segments: 0,
+                ..Torus::new(1.0, 0.25)
+            }
+            .generate(),
+        ]
+    }
+
+    #[test]
+    fn zero_counts_are_clamped() {
+        for mesh in zero_count_shapes() {
+            assert!(!mesh.triangles.is_empty());
+
+            for vertex in &mesh.vertices {
+                let uv = vertex.uv_or_zero();
+
+                assert!(uv.x.is_finite() && uv.y.is_finite());
+                assert!(vertex.position.x.is_finite() && vertex.position.y.is_finite());
+            }
+        }
+    }
+
+    #[cfg(feature = "tangents")]
+    #[test]
+    fn shapes_have_tangents() {
+        let mesh = UvSphere::new(1.0).generate();
+
+        assert!(mesh.vertices.iter().all(|vertex| vertex.tangent.is_some()));
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    collections::HashMap,
+    f32::consts::{PI, TAU},
+};
+
+use cgmath::{InnerSpace, Vector2, Vector3};
+
+use super::{ProfilePoint, Shape, ShapeMesh};
+
+/// A sphere made of rings and segments like a globe.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UvSphere {
+    pub radius: f32,
+    /// The number of segments around the y axis
+    pub segments: u32,
+    /// The number of rows from pole to pole
+    pub rings: u32,
+}
+
+/// A subdivided icosahedron, the triangles are more uniform than the ones of a [`UvSphere`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Icosphere {
+    pub radius: f32,
+    /// How often every triangle is split into four, the triangle count is `20 * 4^subdivisions`
+    pub subdivisions: u32,
+}
+
+impl UvSphere {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            segments: 32,
+            rings: 16,
+        }
+    }
+
+    pub fn with_segments(self, segments: u32) -> Self {
+        Self {
+            segments: segments.max(3),
+            ..self
+        }
+    }
+
+    pub fn with_rings(self, rings: u32) -> Self {
+        Self {
+            rings: rings.max(2),
+            ..self
+        }
+    }
+}
+
+impl Icosphere {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            subdivisions: 2,
+        }
+    }
+
+    pub fn with_subdivisions(self, subdivisions: u32) -> Self {
+        Self {
+            subdivisions,
+            ..self
+        }
+    }
+}
+
+impl Shape for UvSphere {
+    fn generate(&self) -> ShapeMesh {
+        let sphere = self.with_segments(self.segments).with_rings(self.rings);
+
+        let mut mesh = ShapeMesh::new();
+
+        let profile = (0..=sphere.rings)
+            .map(|ring| {
+                let v = ring as f32 / sphere.rings as f32;
+                let (sin, cos) = (v * PI).sin_cos();
+
+                ProfilePoint {
+                    radius: sphere.radius * sin,
+                    y: sphere.radius * cos,
+                    normal: Vector2::new(sin, cos),
+                    v,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        mesh.push_revolution(sphere.segments, &profile);
+
+        mesh.with_tangents()
+    }
+}
+
+impl Shape for Icosphere {
+    fn generate(&self) -> ShapeMesh {
+        let (directions, triangles) = icosphere(self.subdivisions);
+
+        // Texture coordinates like the ones of a `UvSphere`
+        let uv = |direction: Vector3<f32>| {
+            Vector2::new(
+                (direction.x.atan2(direction.z) / TAU).rem_euclid(1.0),
+                direction.y.clamp(-1.0, 1.0).acos() / PI,
+            )
+        };
+
+        let mut mesh = ShapeMesh::new();
+        // Vertices on the seam or the poles are duplicated for every distinct u
+        let mut vertices = HashMap::<(u32, u32), u32>::new();
+
+        for triangle in triangles {
+            let mut uvs = triangle.map(|idx| uv(directions[idx as usize]));
+
+            // Triangles crossing the seam continue past u = 1
+            let (min_u, max_u) = uvs.iter().fold((f32::MAX, f32::MIN), |(min, max), uv| {
+                (min.min(uv.x), max.max(uv.x))
+            });
+
+            if max_u - min_u > 0.5 {
+                for uv in &mut uvs {
+                    if uv.x < 0.5 {
+                        uv.x += 1.0;
+                    }
+                }
+            }
+
+            // The u of a pole is undefined, take the one of the opposite edge
+            for corner in 0..3 {
+                if directions[triangle[corner] as usize].y.abs() > 1.0 - 1e-6 {
+                    uvs[corner].x = (uvs[(corner + 1) % 3].x + uvs[(corner + 2) % 3].x) / 2.0;
+                }
+            }
+
+            let indices = [0, 1, 2].map(|corner| {
+                let direction_idx = triangle[corner];
+                let uv = uvs[corner];
+
+                *vertices
+                    .entry((direction_idx, uv.x.to_bits()))
+                    .or_insert_with(|| {
+                        let direction = directions[direction_idx as usize];
+                        mesh.push_vertex(direction * self.radius, direction, uv)
+                    })
+            });
+
+            mesh.push_triangle(indices);
+        }
+
+        mesh.with_tangents()
+    }
+}
+
+/// The unit directions and triangles of a subdivided icosahedron.
+fn icosphere(subdivisions: u32) -> (Vec<Vector3<f32>>, Vec<[u32; 3]>) {
+    let phi = (1.0 + 5f32.sqrt()) / 2.0;
+
+    let mut directions = [
+        [-1.0, phi, 0.0],
+        [1.0, phi, 0.0],
+        [-1.0, -phi, 0.0],
+        [1.0, -phi, 0.0],
+        [0.0, -1.0, phi],
+        [0.0, 1.0, phi],
+        [0.0, -1.0, -phi],
+        [0.0, 1.0, -phi],
+        [phi, 0.0, -1.0],
+        [phi, 0.0, 1.0],
+        [-phi, 0.0, -1.0],
+        [-phi, 0.0, 1.0],
+    ]
+    .map(|direction| Vector3::from(direction).normalize())
+    .to_vec();
+
+    let mut triangles = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoints = HashMap::<(u32, u32), u32>::new();
+
+        let mut midpoint = |a: u32, b: u32| {
+            *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                let direction = (directions[a as usize] + directions[b as usize]).normalize();
+                directions.push(direction);
+                directions.len() as u32 - 1
+            })
+        };
+
+        triangles = triangles
+            .into_iter()
+            .flat_map(|[a, b, c]| {
+                let ab = midpoint(a, b);
+                let bc = midpoint(b, c);
+                let ca = midpoint(c, a);
+
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+
+    (directions, triangles)
+}
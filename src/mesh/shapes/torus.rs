@@ -0,0 +1,72 @@
+use std::f32::consts::TAU;
+
+use cgmath::Vector2;
+
+use super::{ProfilePoint, Shape, ShapeMesh};
+
+/// A ring around the y axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Torus {
+    /// The distance from the center to the middle of the tube
+    pub major_radius: f32,
+    /// The radius of the tube
+    pub minor_radius: f32,
+    /// The number of segments around the y axis
+    pub major_segments: u32,
+    /// The number of segments around the tube
+    pub minor_segments: u32,
+}
+
+impl Torus {
+    pub fn new(major_radius: f32, minor_radius: f32) -> Self {
+        Self {
+            major_radius,
+            minor_radius,
+            major_segments: 32,
+            minor_segments: 16,
+        }
+    }
+
+    pub fn with_major_segments(self, major_segments: u32) -> Self {
+        Self {
+            major_segments: major_segments.max(3),
+            ..self
+        }
+    }
+
+    pub fn with_minor_segments(self, minor_segments: u32) -> Self {
+        Self {
+            minor_segments: minor_segments.max(3),
+            ..self
+        }
+    }
+}
+
+impl Shape for Torus {
+    fn generate(&self) -> ShapeMesh {
+        let torus = self
+            .with_major_segments(self.major_segments)
+            .with_minor_segments(self.minor_segments);
+
+        let mut mesh = ShapeMesh::new();
+
+        // Around the tube starting at the top, then outwards
+        let profile = (0..=torus.minor_segments)
+            .map(|segment| {
+                let v = segment as f32 / torus.minor_segments as f32;
+                let (sin, cos) = (v * TAU).sin_cos();
+
+                ProfilePoint {
+                    radius: torus.major_radius + torus.minor_radius * sin,
+                    y: torus.minor_radius * cos,
+                    normal: Vector2::new(sin, cos),
+                    v,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        mesh.push_revolution(torus.major_segments, &profile);
+
+        mesh.with_tangents()
+    }
+}
@@ -0,0 +1,28 @@
+use std::ops::Range;
+
+/// A named draw range of a [`Mesh`](super::Mesh), e.g. the triangles of one material.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submesh {
+    pub name: String,
+    /// The range of the index buffer, or of the vertex buffer for unindexed meshes
+    pub indices: Range<u32>,
+    /// Added to every index, lets submeshes with separate vertices use indices starting at 0
+    pub base_vertex: i32,
+}
+
+impl Submesh {
+    pub fn new(name: impl Into<String>, indices: Range<u32>) -> Self {
+        Self {
+            name: name.into(),
+            indices,
+            base_vertex: 0,
+        }
+    }
+
+    pub fn with_base_vertex(self, base_vertex: i32) -> Self {
+        Self {
+            base_vertex,
+            ..self
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use wgpu::{IndexFormat, PrimitiveState, PrimitiveTopology};
+
+/// The primitive topology of a [`Pipeline`](crate::shader::Pipeline) or a [`Mesh`](super::Mesh).
+pub trait Topology {
+    const TOPOLOGY: PrimitiveTopology;
+    /// Strips are restarted at the maximum value of this format
+    const STRIP_INDEX_FORMAT: Option<IndexFormat> = None;
+
+    fn primitive_state() -> PrimitiveState {
+        PrimitiveState {
+            topology: Self::TOPOLOGY,
+            strip_index_format: Self::STRIP_INDEX_FORMAT,
+            ..Default::default()
+        }
+    }
+}
+
+pub struct PointList;
+pub struct LineList;
+pub struct TriangleList;
+pub struct LineStrip;
+pub struct TriangleStrip;
+
+impl Topology for PointList {
+    const TOPOLOGY: PrimitiveTopology = PrimitiveTopology::PointList;
+}
+
+impl Topology for LineList {
+    const TOPOLOGY: PrimitiveTopology = PrimitiveTopology::LineList;
+}
+
+impl Topology for TriangleList {
+    const TOPOLOGY: PrimitiveTopology = PrimitiveTopology::TriangleList;
+}
+
+impl Topology for LineStrip {
+    const TOPOLOGY: PrimitiveTopology = PrimitiveTopology::LineStrip;
+    const STRIP_INDEX_FORMAT: Option<IndexFormat> = Some(IndexFormat::Uint32);
+}
+
+impl Topology for TriangleStrip {
+    const TOPOLOGY: PrimitiveTopology = PrimitiveTopology::TriangleStrip;
+    const STRIP_INDEX_FORMAT: Option<IndexFormat> = Some(IndexFormat::Uint32);
+}
+
+/// The indices of a mesh are a list of primitives
+pub struct List;
+/// The indices of a mesh are strips separated by primitive restarts
+pub struct Strip;
+
+/// Maps the `PRIMITIVE_SIZE` of a [`Mesh`](super::Mesh) to its [`Topology`].
+pub struct PrimitiveSize<const PRIMITIVE_SIZE: usize>;
+
+/// Implemented by [`PrimitiveSize`] for every valid combination of primitive size and `K` ([`List`] or [`Strip`]).
+pub trait MeshTopology<K> {
+    type Topology: Topology;
+}
+
+impl MeshTopology<List> for PrimitiveSize<1> {
+    type Topology = PointList;
+}
+
+impl MeshTopology<List> for PrimitiveSize<2> {
+    type Topology = LineList;
+}
+
+impl MeshTopology<List> for PrimitiveSize<3> {
+    type Topology = TriangleList;
+}
+
+impl MeshTopology<Strip> for PrimitiveSize<2> {
+    type Topology = LineStrip;
+}
+
+impl MeshTopology<Strip> for PrimitiveSize<3> {
+    type Topology = TriangleStrip;
+}
@@ -0,0 +1,77 @@
+use cgmath::{Vector2, Vector3, Vector4};
+
+use super::MeshAttributes;
+
+/// Reads and writes the attributes of a vertex, used to process a [`MeshData`](super::MeshData).
+///
+/// Can be derived with the same field attributes as [`FromMeshAttributes`](super::FromMeshAttributes), a `#[position]`
+/// field is required. The getters of missing attributes return `None` and their setters do nothing.
+pub trait VertexAttributes: Clone {
+    fn position(&self) -> Vector3<f32>;
+    fn set_position(&mut self, value: Vector3<f32>);
+
+    fn normal(&self) -> Option<Vector3<f32>>;
+    fn set_normal(&mut self, value: Vector3<f32>);
+
+    fn uv(&self) -> Option<Vector2<f32>>;
+    fn set_uv(&mut self, value: Vector2<f32>);
+
+    fn tangent(&self) -> Option<Vector4<f32>>;
+    fn set_tangent(&mut self, value: Vector4<f32>);
+
+    fn joints(&self) -> Option<Vector4<u32>>;
+    fn set_joints(&mut self, value: Vector4<u32>);
+
+    fn weights(&self) -> Option<Vector4<f32>>;
+    fn set_weights(&mut self, value: Vector4<f32>);
+}
+
+impl VertexAttributes for MeshAttributes {
+    fn position(&self) -> Vector3<f32> {
+        self.position
+    }
+
+    fn set_position(&mut self, value: Vector3<f32>) {
+        self.position = value;
+    }
+
+    fn normal(&self) -> Option<Vector3<f32>> {
+        self.normal
+    }
+
+    fn set_normal(&mut self, value: Vector3<f32>) {
+        self.normal = Some(value);
+    }
+
+    fn uv(&self) -> Option<Vector2<f32>> {
+        self.uv
+    }
+
+    fn set_uv(&mut self, value: Vector2<f32>) {
+        self.uv = Some(value);
+    }
+
+    fn tangent(&self) -> Option<Vector4<f32>> {
+        self.tangent
+    }
+
+    fn set_tangent(&mut self, value: Vector4<f32>) {
+        self.tangent = Some(value);
+    }
+
+    fn joints(&self) -> Option<Vector4<u32>> {
+        self.joints
+    }
+
+    fn set_joints(&mut self, value: Vector4<u32>) {
+        self.joints = Some(value);
+    }
+
+    fn weights(&self) -> Option<Vector4<f32>> {
+        self.weights
+    }
+
+    fn set_weights(&mut self, value: Vector4<f32>) {
+        self.weights = Some(value);
+    }
+}
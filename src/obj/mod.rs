@@ -0,0 +1,7 @@
+mod mtl;
+mod obj;
+mod obj_mesh;
+
+pub use mtl::ObjMaterial;
+pub use obj::{Obj, ObjError, ObjFace, ObjGroup, ObjVertex};
+pub use obj_mesh::{ObjMesh, ObjMeshGroup};
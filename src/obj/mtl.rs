@@ -0,0 +1,206 @@
+use cgmath::Vector3;
+
+use super::{
+    ObjError,
+    obj::{logical_lines, parse_float, parse_floats},
+};
+
+/// A material from a Wavefront MTL file.
+///
+/// Texture paths are relative to the MTL file, texture options like `-bm` are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjMaterial {
+    pub name: String,
+    /// `Ka`
+    pub ambient: Vector3<f32>,
+    /// `Kd`
+    pub diffuse: Vector3<f32>,
+    /// `Ks`
+    pub specular: Vector3<f32>,
+    /// `Ke`
+    pub emissive: Vector3<f32>,
+    /// `Ns`
+    pub shininess: f32,
+    /// `d`, or `1 - Tr`
+    pub opacity: f32,
+    /// `Ni`
+    pub optical_density: f32,
+    /// `illum`
+    pub illumination_model: Option<u32>,
+
+    /// `map_Ka`
+    pub ambient_texture: Option<String>,
+    /// `map_Kd`
+    pub diffuse_texture: Option<String>,
+    /// `map_Ks`
+    pub specular_texture: Option<String>,
+    /// `map_Ke`
+    pub emissive_texture: Option<String>,
+    /// `map_Bump`, `bump` or `norm`
+    pub normal_texture: Option<String>,
+    /// `map_d`
+    pub opacity_texture: Option<String>,
+}
+
+impl ObjMaterial {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ambient: Vector3::new(0.0, 0.0, 0.0),
+            diffuse: Vector3::new(1.0, 1.0, 1.0),
+            specular: Vector3::new(0.0, 0.0, 0.0),
+            emissive: Vector3::new(0.0, 0.0, 0.0),
+            shininess: 0.0,
+            opacity: 1.0,
+            optical_density: 1.0,
+            illumination_model: None,
+            ambient_texture: None,
+            diffuse_texture: None,
+            specular_texture: None,
+            emissive_texture: None,
+            normal_texture: None,
+            opacity_texture: None,
+        }
+    }
+
+    /// Parses all materials of an MTL file.
+    pub fn parse(source: &str) -> Result<Vec<Self>, ObjError> {
+        let mut materials: Vec<Self> = Vec::new();
+
+        for (line_idx, line) in logical_lines(source) {
+            let parse_err = |message: String| ObjError::Parse {
+                line: line_idx + 1,
+                message,
+            };
+
+            let mut tokens = line.split_whitespace();
+
+            let Some(keyword) = tokens.next() else {
+                continue;
+            };
+
+            if keyword == "newmtl" {
+                materials.push(Self::new(tokens.collect::<Vec<_>>().join(" ")));
+                continue;
+            }
+
+            let Some(material) = materials.last_mut() else {
+                return Err(parse_err(format!(
+                    "`{}` before the first `newmtl`",
+                    keyword
+                )));
+            };
+
+            let color = |tokens: &mut std::str::SplitWhitespace| {
+                parse_floats::<3>(tokens).map(|[r, g, b]| Vector3::new(r, g, b))
+            };
+
+            match keyword {
+                "Ka" => material.ambient = color(&mut tokens).map_err(parse_err)?,
+                "Kd" => material.diffuse = color(&mut tokens).map_err(parse_err)?,
+                "Ks" => material.specular = color(&mut tokens).map_err(parse_err)?,
+                "Ke" => material.emissive = color(&mut tokens).map_err(parse_err)?,
+                "Ns" => material.shininess = parse_float(tokens.next(), "Ns").map_err(parse_err)?,
+                "Ni" => {
+                    material.optical_density =
+                        parse_float(tokens.next(), "Ni").map_err(parse_err)?
+                }
+                "d" => material.opacity = parse_float(tokens.next(), "d").map_err(parse_err)?,
+                "Tr" => {
+                    material.opacity = 1.0 - parse_float(tokens.next(), "Tr").map_err(parse_err)?
+                }
+                "illum" => {
+                    let illum = tokens.next().unwrap_or_default();
+                    material.illumination_model = Some(illum.parse().map_err(|_| {
+                        parse_err(format!("invalid illumination model `{}`", illum))
+                    })?);
+                }
+                "map_Ka" => material.ambient_texture = texture_path(tokens),
+                "map_Kd" => material.diffuse_texture = texture_path(tokens),
+                "map_Ks" => material.specular_texture = texture_path(tokens),
+                "map_Ke" => material.emissive_texture = texture_path(tokens),
+                "map_Bump" | "map_bump" | "bump" | "norm" => {
+                    material.normal_texture = texture_path(tokens)
+                }
+                "map_d" => material.opacity_texture = texture_path(tokens),
+                // Transmission filter, reflection maps, PBR extensions, ...
+                _ => {}
+            }
+        }
+
+        Ok(materials)
+    }
+}
+
+/// The file name is the last argument, everything before it are options.
+fn texture_path(tokens: std::str::SplitWhitespace) -> Option<String> {
+    tokens.last().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Vector3;
+
+    use super::ObjMaterial;
+    use crate::obj::ObjError;
+
+    #[test]
+    fn materials() {
+        let materials = ObjMaterial::parse(
+            "
+            # two materials
+            newmtl red paint
+            Kd 1 0 0
+            Ks 0.5 0.5 0.5
+            Ns 10
+            Tr 0.25
+            illum 2
+            map_Kd -bm 1 textures/red.png
+            norm red_normal.png
+            Pr 0.5
+
+            newmtl glass
+            d 0.1
+            Ni 1.5
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(materials.len(), 2);
+
+        let red = &materials[0];
+        assert_eq!(red.name, "red paint");
+        assert_eq!(red.diffuse, Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(red.specular, Vector3::new(0.5, 0.5, 0.5));
+        assert_eq!(red.shininess, 10.0);
+        assert_eq!(red.opacity, 0.75);
+        assert_eq!(red.illumination_model, Some(2));
+        assert_eq!(red.diffuse_texture.as_deref(), Some("textures/red.png"));
+        assert_eq!(red.normal_texture.as_deref(), Some("red_normal.png"));
+
+        let glass = &materials[1];
+        assert_eq!(glass.opacity, 0.1);
+        assert_eq!(glass.optical_density, 1.5);
+        assert_eq!(glass.diffuse, ObjMaterial::new(String::new()).diffuse);
+    }
+
+    #[test]
+    fn statements_before_newmtl_fail() {
+        let result = ObjMaterial::parse("\nKd 1 0 0\nnewmtl red");
+
+        assert!(matches!(result, Err(ObjError::Parse { line: 2, .. })));
+    }
+
+    #[test]
+    fn invalid_values_fail() {
+        for source in ["newmtl a\nKd 1 0", "newmtl a\nNs x", "newmtl a\nillum -1"] {
+            assert!(
+                matches!(
+                    ObjMaterial::parse(source),
+                    Err(ObjError::Parse { line: 2, .. })
+                ),
+                "{source}"
+            );
+        }
+    }
+}
@@ -0,0 +1,432 @@
+use std::{fmt::Display, path::Path, str::SplitWhitespace};
+
+use cgmath::{Vector2, Vector3};
+
+use super::{ObjMaterial, ObjMesh};
+
+/// The content of a Wavefront OBJ file.
+///
+/// Only polygonal faces are read, lines, points and free-form geometry are skipped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Obj {
+    pub positions: Vec<Vector3<f32>>,
+    pub normals: Vec<Vector3<f32>>,
+    pub uvs: Vec<Vector2<f32>>,
+    /// A new group starts at every `g`, `o` and `usemtl` statement, groups without faces are skipped
+    pub groups: Vec<ObjGroup>,
+    /// The files referenced by `mtllib` statements
+    pub material_libs: Vec<String>,
+    /// Only filled by [`Obj::load`], use [`ObjMaterial::parse`] to add materials to a parsed OBJ
+    pub materials: Vec<ObjMaterial>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjGroup {
+    /// The name of the last `g` or `o` statement, empty for faces before the first one
+    pub name: String,
+    /// The name of the material from the last `usemtl` statement
+    pub material: Option<String>,
+    pub faces: Vec<ObjFace>,
+}
+
+/// A polygon with at least 3 vertices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjFace {
+    pub vertices: Vec<ObjVertex>,
+}
+
+/// Indices into [`Obj::positions`], [`Obj::uvs`] and [`Obj::normals`], relative indices are already resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjVertex {
+    pub position: u32,
+    pub uv: Option<u32>,
+    pub normal: Option<u32>,
+}
+
+#[derive(Debug)]
+pub enum ObjError {
+    Io(std::io::Error),
+    /// `line` starts at 1
+    Parse {
+        line: usize,
+        message: String,
+    },
+}
+
+impl Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjError::Io(err) => write!(f, "failed to read obj file: {}", err),
+            ObjError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ObjError::Io(err) => Some(err),
+            ObjError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ObjError {
+    fn from(err: std::io::Error) -> Self {
+        ObjError::Io(err)
+    }
+}
+
+impl Obj {
+    /// Reads the OBJ file at `path` and the MTL files it references, relative to the directory of `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ObjError> {
+        let path = path.as_ref();
+        let mut obj = Self::parse(&std::fs::read_to_string(path)?)?;
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        for lib in &obj.material_libs {
+            let source = std::fs::read_to_string(dir.join(lib))?;
+            obj.materials.extend(ObjMaterial::parse(&source)?);
+        }
+
+        Ok(obj)
+    }
+
+    pub fn parse(source: &str) -> Result<Self, ObjError> {
+        let mut obj = Obj::default();
+        let mut group = ObjGroup::default();
+
+        for (line_idx, line) in logical_lines(source) {
+            let parse_err = |message: String| ObjError::Parse {
+                line: line_idx + 1,
+                message,
+            };
+
+            let mut tokens = line.split_whitespace();
+
+            let Some(keyword) = tokens.next() else {
+                continue;
+            };
+
+            match keyword {
+                "v" => {
+                    let [x, y, z] = parse_floats(&mut tokens).map_err(parse_err)?;
+                    obj.positions.push(Vector3::new(x, y, z));
+                }
+                "vn" => {
+                    let [x, y, z] = parse_floats(&mut tokens).map_err(parse_err)?;
+                    obj.normals.push(Vector3::new(x, y, z));
+                }
+                "vt" => {
+                    let u = parse_float(tokens.next(), "u").map_err(parse_err)?;
+                    // `v` is optional
+                    let v = tokens.next().map_or(Ok(0.0), |v| parse_float(Some(v), "v"));
+                    obj.uvs.push(Vector2::new(u, v.map_err(parse_err)?));
+                }
+                "f" => {
+                    let vertices = tokens
+                        .map(|token| obj.parse_vertex(token))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(parse_err)?;
+
+                    if vertices.len() < 3 {
+                        return Err(parse_err(format!(
+                            "a face needs at least 3 vertices, got {}",
+                            vertices.len()
+                        )));
+                    }
+
+                    group.faces.push(ObjFace { vertices });
+                }
+                "g" | "o" => {
+                    let name = tokens.collect::<Vec<_>>().join(" ");
+                    obj.finish_group(&mut group);
+                    group.name = name;
+                }
+                "usemtl" => {
+                    let material = tokens.collect::<Vec<_>>().join(" ");
+                    obj.finish_group(&mut group);
+                    group.material = Some(material);
+                }
+                "mtllib" => obj.material_libs.extend(tokens.map(String::from)),
+                // Smoothing groups, lines, points, free-form geometry, ...
+                _ => {}
+            }
+        }
+
+        obj.finish_group(&mut group);
+
+        Ok(obj)
+    }
+
+    /// Deduplicates the vertices and triangulates the faces, see [`ObjMesh`].
+    pub fn mesh(&self) -> ObjMesh {
+        ObjMesh::new(self)
+    }
+
+    /// Parses a face vertex `v`, `v/vt`, `v//vn` or `v/vt/vn`.
+    fn parse_vertex(&self, token: &str) -> Result<ObjVertex, String> {
+        let mut parts = token.split('/');
+
+        let position = parts.next().unwrap_or_default();
+        let uv = parts.next().filter(|uv| !uv.is_empty());
+        let normal = parts.next().filter(|normal| !normal.is_empty());
+
+        Ok(ObjVertex {
+            position: resolve_index(position, self.positions.len())?,
+            uv: uv.map(|uv| resolve_index(uv, self.uvs.len())).transpose()?,
+            normal: normal
+                .map(|normal| resolve_index(normal, self.normals.len()))
+                .transpose()?,
+        })
+    }
+
+    /// Adds `group` if it has faces, the next group keeps its name and material.
+    fn finish_group(&mut self, group: &mut ObjGroup) {
+        if !group.faces.is_empty() {
+            self.groups.push(ObjGroup {
+                name: group.name.clone(),
+                material: group.material.clone(),
+                faces: std::mem::take(&mut group.faces),
+            });
+        }
+    }
+}
+
+/// Lines with comments removed and `\` continuations joined, with the index of their first line.
+pub(super) fn logical_lines(source: &str) -> impl Iterator<Item = (usize, String)> + '_ {
+    let mut lines = source.lines().enumerate();
+
+    std::iter::from_fn(move || {
+        let (start, first) = lines.next()?;
+        let mut line = String::from(first);
+
+        while line.trim_end().ends_with('\\') {
+            let trimmed = line.trim_end().len() - 1;
+            line.truncate(trimmed);
+
+            match lines.next() {
+                Some((_, next)) => line.push_str(next),
+                None => break,
+            }
+        }
+
+        if let Some(comment) = line.find('#') {
+            line.truncate(comment);
+        }
+
+        Some((start, line))
+    })
+}
+
+pub(super) fn parse_float(token: Option<&str>, name: &str) -> Result<f32, String> {
+    let token = token.ok_or_else(|| format!("missing {}", name))?;
+
+    token
+        .parse()
+        .map_err(|_| format!("invalid number `{}` for {}", token, name))
+}
+
+/// Parses the next `N` numbers, ignoring everything after them.
+pub(super) fn parse_floats<const N: usize>(
+    tokens: &mut SplitWhitespace,
+) -> Result<[f32; N], String> {
+    let mut values = [0.0; N];
+
+    for (idx, value) in values.iter_mut().enumerate() {
+        *value = parse_float(tokens.next(), &format!("component {}", idx))?;
+    }
+
+    Ok(values)
+}
+
+/// Converts the 1-based or negative relative `index` into a 0-based index into a list with `len` elements.
+fn resolve_index(index: &str, len: usize) -> Result<u32, String> {
+    let parsed: i64 = index
+        .parse()
+        .map_err(|_| format!("invalid index `{}`", index))?;
+
+    let resolved = match parsed {
+        1.. => parsed - 1,
+        ..0 => len as i64 + parsed,
+        0 => return Err("indices start at 1".to_string()),
+    };
+
+    if resolved < 0 || resolved >= len as i64 {
+        return Err(format!(
+            "index {} is out of bounds, there are {} elements",
+            index, len
+        ));
+    }
+
+    Ok(resolved as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Vector2, Vector3};
+
+    use super::{Obj, ObjError, ObjVertex};
+
+    const TRIANGLE: &str = "
+        v 0 0 0
+        v 1 0 0
+        v 0 1 0
+        vt 0 0
+        vt 1 0
+        vt 0 1
+        vn 0 0 1
+    ";
+
+    fn vertex(position: u32, uv: Option<u32>, normal: Option<u32>) -> ObjVertex {
+        ObjVertex {
+            position,
+            uv,
+            normal,
+        }
+    }
+
+    /// The line and message of a parse error.
+    fn parse_error(source: &str) -> (usize, String) {
+        match Obj::parse(source) {
+            Err(ObjError::Parse { line, message }) => (line, message),
+            result => panic!("expected a parse error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn vertex_forms() {
+        let obj = Obj::parse(&format!(
+            "{TRIANGLE}\nf 1 2 3\nf 1/1 2/2 3/3\nf 1//1 2//1 3//1\nf 1/1/1 2/2/1 3/3/1"
+        ))
+        .unwrap();
+
+        let faces = &obj.groups[0].faces;
+
+        assert_eq!(faces[0].vertices[1], vertex(1, None, None));
+        assert_eq!(faces[1].vertices[1], vertex(1, Some(1), None));
+        assert_eq!(faces[2].vertices[1], vertex(1, None, Some(0)));
+        assert_eq!(faces[3].vertices[1], vertex(1, Some(1), Some(0)));
+
+        assert_eq!(obj.positions[1], Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(obj.uvs[2], Vector2::new(0.0, 1.0));
+        assert_eq!(obj.normals[0], Vector3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn relative_indices_count_back_from_the_last_element() {
+        let obj = Obj::parse(&format!(
+            "{TRIANGLE}\nf -3/-3/-1 -2/-2/-1 -1/-1/-1\nv 5 5 5\nf -4 -3 -1"
+        ))
+        .unwrap();
+
+        let faces = &obj.groups[0].faces;
+
+        assert_eq!(
+            faces[0].vertices,
+            [
+                vertex(0, Some(0), Some(0)),
+                vertex(1, Some(1), Some(0)),
+                vertex(2, Some(2), Some(0))
+            ]
+        );
+
+        // Relative to the positions declared so far
+        assert_eq!(
+            faces[1].vertices,
+            [
+                vertex(0, None, None),
+                vertex(1, None, None),
+                vertex(3, None, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_indices_fail() {
+        let (line, message) = parse_error(&format!("{TRIANGLE}\nf 1 2 4"));
+        assert_eq!(line, 10);
+        assert!(message.contains("out of bounds"), "{message}");
+
+        let (_, message) = parse_error(&format!("{TRIANGLE}\nf 0 1 2"));
+        assert!(message.contains("start at 1"), "{message}");
+
+        let (_, message) = parse_error(&format!("{TRIANGLE}\nf -4 1 2"));
+        assert!(message.contains("out of bounds"), "{message}");
+
+        let (_, message) = parse_error(&format!("{TRIANGLE}\nf 1/4 2 3"));
+        assert!(message.contains("out of bounds"), "{message}");
+
+        let (_, message) = parse_error(&format!("{TRIANGLE}\nf 1 a 3"));
+        assert!(message.contains("invalid index `a`"), "{message}");
+    }
+
+    #[test]
+    fn malformed_statements_fail() {
+        assert_eq!(parse_error("v 1 2").0, 1);
+        assert!(parse_error("\nvn 1 x 0").1.contains("invalid number `x`"));
+        assert!(
+            parse_error(&format!("{TRIANGLE}\nf 1 2"))
+                .1
+                .contains("at least 3")
+        );
+    }
+
+    #[test]
+    fn groups_and_materials() {
+        let obj = Obj::parse(&format!(
+            "{TRIANGLE}
+            mtllib scene.mtl
+            f 1 2 3
+            o box
+            usemtl red
+            f 1 2 3
+            f 3 2 1
+            usemtl blue
+            g empty
+            g lid
+            f 1 2 3"
+        ))
+        .unwrap();
+
+        let groups = obj
+            .groups
+            .iter()
+            .map(|group| {
+                (
+                    group.name.as_str(),
+                    group.material.as_deref(),
+                    group.faces.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            groups,
+            [
+                ("", None, 1),
+                ("box", Some("red"), 2),
+                ("lid", Some("blue"), 1)
+            ]
+        );
+        assert_eq!(obj.material_libs, ["scene.mtl"]);
+    }
+
+    #[test]
+    fn unknown_statements_and_comments_are_skipped() {
+        let obj = Obj::parse(&format!(
+            "{TRIANGLE}
+            # a comment
+            s 1
+            l 1 2
+            curv 0 1 1 2
+            f 1 2 \\
+              3 # continued"
+        ))
+        .unwrap();
+
+        assert_eq!(obj.groups.len(), 1);
+        assert_eq!(obj.groups[0].faces[0].vertices.len(), 3);
+    }
+}
@@ -0,0 +1,319 @@
+use std::{collections::HashMap, ops::Range};
+
+use cgmath::{InnerSpace, Vector2, Vector3};
+use wgpu::Device;
+
+use crate::{
+    bounds::MeshBounds,
+    mesh::{FromMeshAttributes, Mesh, MeshAttributes, Submesh, Vertex},
+};
+
+use super::{Obj, ObjVertex};
+
+/// The triangulated faces of an [`Obj`] with a shared index buffer.
+///
+/// Face vertices with the same position, texture coordinates and normal become a single vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjMesh {
+    pub vertices: Vec<MeshAttributes>,
+    pub triangles: Vec<[u32; 3]>,
+    pub groups: Vec<ObjMeshGroup>,
+}
+
+/// The triangles of an [`ObjGroup`](super::ObjGroup).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjMeshGroup {
+    pub name: String,
+    pub material: Option<String>,
+    /// The range in [`ObjMesh::triangles`]
+    pub triangles: Range<u32>,
+}
+
+impl ObjMesh {
+    pub(super) fn new(obj: &Obj) -> Self {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+        let mut groups = Vec::with_capacity(obj.groups.len());
+
+        let mut indices = HashMap::<ObjVertex, u32>::new();
+
+        for group in &obj.groups {
+            let start = triangles.len() as u32;
+
+            for face in &group.faces {
+                let face_indices = face
+                    .vertices
+                    .iter()
+                    .map(|vertex| {
+                        *indices.entry(*vertex).or_insert_with(|| {
+                            vertices.push(MeshAttributes {
+                                position: obj.positions[vertex.position as usize],
+                                normal: vertex.normal.map(|normal| obj.normals[normal as usize]),
+                                uv: vertex.uv.map(|uv| obj.uvs[uv as usize]),
+                                tangent: None,
+                                joints: None,
+                                weights: None,
+                            });
+
+                            vertices.len() as u32 - 1
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                let positions = face
+                    .vertices
+                    .iter()
+                    .map(|vertex| obj.positions[vertex.position as usize])
+                    .collect::<Vec<_>>();
+
+                triangles.extend(
+                    triangulate(&positions)
+                        .into_iter()
+                        .map(|triangle| triangle.map(|idx| face_indices[idx])),
+                );
+            }
+
+            groups.push(ObjMeshGroup {
+                name: group.name.clone(),
+                material: group.material.clone(),
+                triangles: start..triangles.len() as u32,
+            });
+        }
+
+        Self {
+            vertices,
+            triangles,
+            groups,
+        }
+    }
+
+    pub fn to_vertices<V: FromMeshAttributes>(&self) -> Vec<V> {
+        self.vertices.iter().map(V::from_mesh_attributes).collect()
+    }
+
+    /// Uploads all groups into a single mesh with one [`Submesh`] per group.
+    pub fn to_mesh<V: Vertex + FromMeshAttributes>(
+        &self,
+        device: &Device,
+        label: Option<&str>,
+    ) -> Mesh<V> {
+        let submeshes = self.groups.iter().map(|group| {
+            Submesh::new(
+                &group.name,
+                group.triangles.start * 3..group.triangles.end * 3,
+            )
+        });
+
+        let mesh = Mesh::new(
+            device,
+            self.to_vertices::<V>(),
+            self.triangles.iter().copied(),
+            label,
+        )
+        .with_submeshes(submeshes);
+
+        match self.bounds() {
+            Some(bounds) => mesh.with_bounds(bounds),
+            None => mesh,
+        }
+    }
+
+    /// The bounds of all vertices, `None` if there are none.
+    pub fn bounds(&self) -> Option<MeshBounds> {
+        MeshBounds::from_attributes(&self.vertices)
+    }
+}
+
+/// Splits a polygon into triangles by ear clipping, which works for convex and concave polygons that don't intersect themselves.
+///
+/// Returns indices into `positions`, the triangles keep the winding order of the polygon.
+fn triangulate(positions: &[Vector3<f32>]) -> Vec<[usize; 3]> {
+    if positions.len() == 3 {
+        return vec![[0, 1, 2]];
+    }
+
+    // Newell's method, works for non-planar polygons as well
+    let normal = (0..positions.len()).fold(Vector3::new(0.0, 0.0, 0.0), |normal, idx| {
+        let a = positions[idx];
+        let b = positions[(idx + 1) % positions.len()];
+
+        normal
+            + Vector3::new(
+                (a.y - b.y) * (a.z + b.z),
+                (a.z - b.z) * (a.x + b.x),
+                (a.x - b.x) * (a.y + b.y),
+            )
+    });
+
+    // Project onto the plane of the largest normal component, mirrored so that the polygon is counter-clockwise
+    let abs = normal.map(f32::abs);
+    let project = |p: Vector3<f32>| {
+        if abs.x >= abs.y && abs.x >= abs.z {
+            Vector2::new(p.y * normal.x.signum(), p.z)
+        } else if abs.y >= abs.z {
+            Vector2::new(p.z * normal.y.signum(), p.x)
+        } else {
+            Vector2::new(p.x * normal.z.signum(), p.y)
+        }
+    };
+
+    let points = positions.iter().map(|p| project(*p)).collect::<Vec<_>>();
+    let area = normal.magnitude();
+
+    let cross = |a: Vector2<f32>, b: Vector2<f32>, c: Vector2<f32>| (b - a).perp_dot(c - a);
+
+    let mut remaining = (0..positions.len()).collect::<Vec<_>>();
+    let mut triangles = Vec::with_capacity(positions.len() - 2);
+
+    while remaining.len() > 3 {
+        let len = remaining.len();
+
+        let ear = (0..len).find(|&idx| {
+            let [a, b, c] = [
+                remaining[(idx + len - 1) % len],
+                remaining[idx],
+                remaining[(idx + 1) % len],
+            ];
+
+            // Convex corner
+            if cross(points[a], points[b], points[c]) <= area * f32::EPSILON {
+                return false;
+            }
+
+            // No other vertex inside of the triangle
+            remaining
+                .iter()
+                .filter(|&&other| other != a && other != b && other != c)
+                .all(|&other| {
+                    let p = points[other];
+
+                    cross(points[a], points[b], p) < 0.0
+                        || cross(points[b], points[c], p) < 0.0
+                        || cross(points[c], points[a], p) < 0.0
+                })
+        });
+
+        match ear {
+            Some(idx) => {
+                triangles.push([
+                    remaining[(idx + len - 1) % len],
+                    remaining[idx],
+                    remaining[(idx + 1) % len],
+                ]);
+                remaining.remove(idx);
+            }
+            // Degenerate or self-intersecting polygon, fall back to a fan
+            None => break,
+        }
+    }
+
+    triangles.extend(
+        (1..remaining.len() - 1).map(|idx| [remaining[0], remaining[idx], remaining[idx + 1]]),
+    );
+
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{InnerSpace, Vector3};
+
+    use super::triangulate;
+    use crate::obj::Obj;
+
+    /// A counter-clockwise L shape with an area of 3 and a reflex corner at `(1, 1)`.
+    const L_SHAPE: [[f32; 2]; 6] = [
+        [0.0, 0.0],
+        [2.0, 0.0],
+        [2.0, 1.0],
+        [1.0, 1.0],
+        [1.0, 2.0],
+        [0.0, 2.0],
+    ];
+
+    /// Checks that the triangles cover the polygon and all face along `normal`.
+    fn assert_triangulated(positions: &[Vector3<f32>], normal: Vector3<f32>) {
+        let triangles = triangulate(positions);
+
+        assert_eq!(triangles.len(), positions.len() - 2);
+
+        let mut area = 0.0;
+
+        for [a, b, c] in triangles {
+            let cross = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+
+            assert!(
+                cross.dot(normal) > 0.0,
+                "triangle {:?} is flipped",
+                [a, b, c]
+            );
+            area += cross.magnitude() / 2.0;
+        }
+
+        assert!(
+            (area - 3.0).abs() < 1e-5,
+            "the triangles cover an area of {area}"
+        );
+    }
+
+    fn test_axis(embed: impl Fn([f32; 2]) -> Vector3<f32>, normal: Vector3<f32>) {
+        let mut positions = L_SHAPE.map(embed).to_vec();
+        assert_triangulated(&positions, normal);
+
+        positions.reverse();
+        assert_triangulated(&positions, -normal);
+    }
+
+    #[test]
+    fn concave_face_along_x() {
+        test_axis(|[u, v]| Vector3::new(0.0, u, v), Vector3::unit_x());
+    }
+
+    #[test]
+    fn concave_face_along_y() {
+        test_axis(|[u, v]| Vector3::new(v, 0.0, u), Vector3::unit_y());
+    }
+
+    #[test]
+    fn concave_face_along_z() {
+        test_axis(|[u, v]| Vector3::new(u, v, 0.0), Vector3::unit_z());
+    }
+
+    #[test]
+    fn material_groups_become_triangle_ranges() {
+        let obj = Obj::parse(
+            "
+            v 0 0 0
+            v 1 0 0
+            v 1 1 0
+            v 0 1 0
+            vn 0 0 1
+            usemtl red
+            f 1 2 3 4
+            usemtl blue
+            f 1//1 2//1 3//1
+            f 1 3 4
+            ",
+        )
+        .unwrap();
+
+        let mesh = obj.mesh();
+        let groups = mesh
+            .groups
+            .iter()
+            .map(|group| {
+                (
+                    group.material.as_deref(),
+                    group.triangles.start,
+                    group.triangles.end,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(groups, [(Some("red"), 0, 2), (Some("blue"), 2, 4)]);
+
+        // Vertices with a normal differ from the ones without
+        assert_eq!(mesh.vertices.len(), 7);
+        assert_eq!(mesh.triangles[3], [0, 2, 3]);
+    }
+}
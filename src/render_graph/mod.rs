@@ -0,0 +1,7 @@
+mod render_graph;
+mod resource;
+mod texture_pool;
+
+pub use render_graph::{PassBuilder, PassResources, RenderGraph, RenderGraphError};
+pub use resource::{ResourceId, TextureHandle, TextureSize};
+pub use texture_pool::TexturePool;
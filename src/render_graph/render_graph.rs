@@ -0,0 +1,546 @@
+use std::{any::Any, fmt::Display};
+
+use cgmath::Vector2;
+use wgpu::{CommandEncoder, Device, TextureFormat};
+
+use crate::{
+    DebugGroupExt,
+    texture::{PixelFormat, RenderTexture},
+};
+
+use super::{
+    TexturePool,
+    resource::{Resource, ResourceId, TextureHandle, TextureKey, TextureSize, TransientTexture},
+    texture_pool::PooledTexture,
+};
+
+type ExecuteFn<'a> = Box<dyn FnOnce(&mut CommandEncoder, &PassResources) + 'a>;
+
+/// A frame described as passes that read and write textures and buffers.
+///
+/// On [`execute`](Self::execute) the passes get sorted by their dependencies, passes that don't contribute to an output get culled
+/// and the transient textures get allocated from a [`TexturePool`]. Transient textures whose lifetimes don't overlap share the same GPU texture.
+pub struct RenderGraph<'a> {
+    output_size: Vector2<u32>,
+    resources: Vec<Resource>,
+    outputs: Vec<ResourceId>,
+    passes: Vec<Pass<'a>>,
+}
+
+struct Pass<'a> {
+    label: &'static str,
+    reads: Vec<ResourceId>,
+    writes: Vec<ResourceId>,
+    execute: ExecuteFn<'a>,
+}
+
+impl<'a> RenderGraph<'a> {
+    /// `output_size` is the size [`TextureSize::Relative`] textures are relative to, usually the size of the surface.
+    pub fn new(output_size: Vector2<u32>) -> Self {
+        Self {
+            output_size,
+            resources: Vec::new(),
+            outputs: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    pub fn output_size(&self) -> Vector2<u32> {
+        self.output_size
+    }
+
+    pub fn create_texture<P: PixelFormat + 'static, const SAMPLES: u32>(
+        &mut self,
+        label: &'static str,
+        size: TextureSize,
+    ) -> TextureHandle<P, SAMPLES> {
+        let id = self.push_resource(Resource::Transient(TransientTexture::new::<P, SAMPLES>(
+            label, size,
+        )));
+
+        TextureHandle::new(id)
+    }
+
+    pub fn create_texture_format<const SAMPLES: u32>(
+        &mut self,
+        label: &'static str,
+        size: TextureSize,
+        format: TextureFormat,
+    ) -> TextureHandle<(), SAMPLES> {
+        let id = self.push_resource(Resource::Transient(
+            TransientTexture::new_format::<SAMPLES>(label, size, format),
+        ));
+
+        TextureHandle::new(id)
+    }
+
+    /// Registers a texture or buffer that lives outside of the graph.
+    ///
+    /// The graph only uses the returned id to order the passes, the passes access the resource directly.
+    pub fn import(&mut self, label: &'static str) -> ResourceId {
+        self.push_resource(Resource::Imported { label })
+    }
+
+    /// Passes that (indirectly) write to an output are never culled.
+    pub fn mark_output(&mut self, id: impl Into<ResourceId>) {
+        self.outputs.push(id.into());
+    }
+
+    pub fn add_pass(&mut self, label: &'static str) -> PassBuilder<'_, 'a> {
+        PassBuilder {
+            graph: self,
+            label,
+            reads: Vec::new(),
+            writes: Vec::new(),
+        }
+    }
+
+    fn push_resource(&mut self, resource: Resource) -> ResourceId {
+        self.resources.push(resource);
+        ResourceId(self.resources.len() - 1)
+    }
+
+    /// Records all passes that are not culled into `encoder`.
+    pub fn execute(
+        self,
+        device: &Device,
+        pool: &mut TexturePool,
+        encoder: &mut CommandEncoder,
+    ) -> Result<(), RenderGraphError> {
+        let order = self.compile()?;
+
+        let (slots, assigned) = self.allocate_textures(device, pool, &order);
+
+        let resources = PassResources {
+            slots: &slots,
+            assigned: &assigned,
+        };
+
+        let mut passes = self.passes.into_iter().map(Some).collect::<Vec<_>>();
+
+        for idx in order {
+            if let Some(pass) = passes[idx].take() {
+                let mut encoder = encoder.debug_group(pass.label);
+                (pass.execute)(&mut encoder, &resources);
+            }
+        }
+
+        // Textures of the previous frame that were not reused get dropped here
+        pool.textures = slots
+            .into_iter()
+            .map(|slot| PooledTexture {
+                key: slot.key,
+                texture: slot.texture,
+            })
+            .collect();
+
+        Ok(())
+    }
+
+    /// The indices of all passes that are not culled in execution order.
+    ///
+    /// A pass reads the version of a resource written by the last writer added before it, or the final version if no
+    /// writer was added before it. Writers of a resource run in the order they were added, each after the readers of
+    /// the previous version.
+    fn compile(&self) -> Result<Vec<usize>, RenderGraphError> {
+        let mut writers = vec![Vec::new(); self.resources.len()];
+
+        for (idx, pass) in self.passes.iter().enumerate() {
+            for id in &pass.writes {
+                writers[id.0].push(idx);
+            }
+        }
+
+        let last_writer_before = |id: ResourceId, idx: usize| {
+            writers[id.0]
+                .iter()
+                .rev()
+                .find(|writer| **writer < idx)
+                .copied()
+        };
+
+        // The passes whose results a pass uses, a write may keep parts of the previous contents
+        let mut dependencies = self
+            .passes
+            .iter()
+            .enumerate()
+            .map(|(idx, pass)| {
+                let read_deps = pass.reads.iter().filter_map(|id| {
+                    last_writer_before(*id, idx).or_else(|| writers[id.0].last().copied())
+                });
+
+                let write_deps = pass
+                    .writes
+                    .iter()
+                    .filter_map(|id| last_writer_before(*id, idx));
+
+                let mut deps = read_deps
+                    .chain(write_deps)
+                    .filter(|dep| *dep != idx)
+                    .collect::<Vec<_>>();
+                deps.sort_unstable();
+                deps.dedup();
+                deps
+            })
+            .collect::<Vec<_>>();
+
+        let mut alive = vec![false; self.passes.len()];
+        let mut stack = self
+            .outputs
+            .iter()
+            .filter_map(|id| writers[id.0].last().copied())
+            .collect::<Vec<_>>();
+
+        while let Some(idx) = stack.pop() {
+            if !alive[idx] {
+                alive[idx] = true;
+                stack.extend(&dependencies[idx]);
+            }
+        }
+
+        for (idx, pass) in self.passes.iter().enumerate() {
+            if !alive[idx] {
+                continue;
+            }
+
+            for id in &pass.reads {
+                if matches!(self.resources[id.0], Resource::Transient(_))
+                    && writers[id.0].is_empty()
+                {
+                    return Err(RenderGraphError::UnwrittenTexture(
+                        self.resources[id.0].label(),
+                    ));
+                }
+
+                // The next writer must not overwrite the version this pass reads
+                let next_writer = last_writer_before(*id, idx)
+                    .and_then(|read| writers[id.0].iter().find(|writer| **writer > read));
+
+                if let Some(next_writer) = next_writer.filter(|writer| **writer != idx) {
+                    dependencies[*next_writer].push(idx);
+                }
+            }
+        }
+
+        let alive_count = alive.iter().filter(|alive| **alive).count();
+
+        let mut done = vec![false; self.passes.len()];
+        let mut order = Vec::with_capacity(alive_count);
+
+        while order.len() < alive_count {
+            let next = (0..self.passes.len()).find(|idx| {
+                alive[*idx] && !done[*idx] && dependencies[*idx].iter().all(|dep| done[*dep])
+            });
+
+            let Some(next) = next else {
+                let passes = (0..self.passes.len())
+                    .filter(|idx| alive[*idx] && !done[*idx])
+                    .map(|idx| self.passes[idx].label)
+                    .collect();
+
+                return Err(RenderGraphError::Cycle(passes));
+            };
+
+            done[next] = true;
+            order.push(next);
+        }
+
+        Ok(order)
+    }
+
+    fn allocate_textures(
+        &self,
+        device: &Device,
+        pool: &mut TexturePool,
+        order: &[usize],
+    ) -> (Vec<TextureSlot>, Vec<Option<usize>>) {
+        let mut lifetimes: Vec<Option<(usize, usize)>> = vec![None; self.resources.len()];
+
+        for (position, idx) in order.iter().enumerate() {
+            let pass = &self.passes[*idx];
+
+            for id in pass.reads.iter().chain(&pass.writes) {
+                let lifetime = lifetimes[id.0].get_or_insert((position, position));
+                lifetime.1 = position;
+            }
+        }
+
+        let mut textures = self
+            .resources
+            .iter()
+            .zip(lifetimes)
+            .enumerate()
+            .filter_map(|(id, (resource, lifetime))| match (resource, lifetime) {
+                (Resource::Transient(texture), Some(lifetime)) => Some((id, texture, lifetime)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        textures.sort_by_key(|(_, _, (first, _))| *first);
+
+        let mut slots: Vec<TextureSlot> = Vec::new();
+        let mut assigned = vec![None; self.resources.len()];
+
+        for (id, texture, (first, last)) in textures {
+            let key = texture.key(self.output_size);
+
+            let free_slot = slots
+                .iter()
+                .position(|slot| slot.key == key && slot.free_from <= first);
+
+            let slot_idx = match free_slot {
+                Some(slot_idx) => {
+                    slots[slot_idx].free_from = last + 1;
+                    slot_idx
+                }
+                None => {
+                    let texture = match pool.take(&key) {
+                        Some(pooled) => pooled.texture,
+                        None => (texture.create)(device, key.size, key.format),
+                    };
+
+                    slots.push(TextureSlot {
+                        key,
+                        texture,
+                        free_from: last + 1,
+                    });
+                    slots.len() - 1
+                }
+            };
+
+            assigned[id] = Some(slot_idx);
+        }
+
+        (slots, assigned)
+    }
+}
+
+pub struct PassBuilder<'g, 'a> {
+    graph: &'g mut RenderGraph<'a>,
+    label: &'static str,
+    reads: Vec<ResourceId>,
+    writes: Vec<ResourceId>,
+}
+
+impl<'a> PassBuilder<'_, 'a> {
+    pub fn reads(mut self, id: impl Into<ResourceId>) -> Self {
+        self.reads.push(id.into());
+        self
+    }
+
+    pub fn writes(mut self, id: impl Into<ResourceId>) -> Self {
+        self.writes.push(id.into());
+        self
+    }
+
+    /// Adds the pass to the graph.
+    ///
+    /// `execute` may only access transient textures that were declared with [`reads`](Self::reads) or [`writes`](Self::writes),
+    /// other textures might share their GPU texture with a texture of this pass.
+    pub fn execute(self, execute: impl FnOnce(&mut CommandEncoder, &PassResources) + 'a) {
+        self.graph.passes.push(Pass {
+            label: self.label,
+            reads: self.reads,
+            writes: self.writes,
+            execute: Box::new(execute),
+        });
+    }
+}
+
+struct TextureSlot {
+    key: TextureKey,
+    texture: Box<dyn Any>,
+    /// The position in the execution order from which on the slot can be reused
+    free_from: usize,
+}
+
+/// Access to the transient textures of a [`RenderGraph`] while it executes.
+pub struct PassResources<'r> {
+    slots: &'r [TextureSlot],
+    assigned: &'r [Option<usize>],
+}
+
+impl PassResources<'_> {
+    pub fn texture<P: 'static, const SAMPLES: u32>(
+        &self,
+        handle: TextureHandle<P, SAMPLES>,
+    ) -> &RenderTexture<P, SAMPLES> {
+        let slot = self.assigned[handle.id.0].expect("texture is not used by any pass");
+
+        self.slots[slot]
+            .texture
+            .downcast_ref()
+            .expect("texture handle belongs to another RenderGraph")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderGraphError {
+    /// The passes depend on each other
+    Cycle(Vec<&'static str>),
+    /// A transient texture is read, but no pass writes to it
+    UnwrittenTexture(&'static str),
+}
+
+impl Display for RenderGraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderGraphError::Cycle(passes) => {
+                write!(
+                    f,
+                    "render graph contains a cycle between {}",
+                    passes.join(", ")
+                )
+            }
+            RenderGraphError::UnwrittenTexture(label) => {
+                write!(f, "transient texture `{}` is read but never written", label)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderGraphError {}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Vector2;
+
+    use crate::texture::ColorPixel;
+
+    use super::*;
+
+    fn add_pass(
+        graph: &mut RenderGraph,
+        label: &'static str,
+        reads: &[ResourceId],
+        writes: &[ResourceId],
+    ) {
+        let pass = reads
+            .iter()
+            .fold(graph.add_pass(label), |pass, id| pass.reads(*id));
+        let pass = writes.iter().fold(pass, |pass, id| pass.writes(*id));
+
+        pass.execute(|_, _| {});
+    }
+
+    fn graph() -> (RenderGraph<'static>, ResourceId, ResourceId, ResourceId) {
+        let mut graph = RenderGraph::new(Vector2::new(64, 64));
+
+        let output = graph.import("output");
+        graph.mark_output(output);
+
+        let a = graph
+            .create_texture::<ColorPixel, 1>("a", TextureSize::Relative(1.0))
+            .id();
+        let b = graph
+            .create_texture::<ColorPixel, 1>("b", TextureSize::Relative(1.0))
+            .id();
+
+        (graph, output, a, b)
+    }
+
+    #[test]
+    fn passes_run_after_their_dependencies() {
+        let (mut graph, output, a, b) = graph();
+
+        add_pass(&mut graph, "a", &[], &[a]);
+        add_pass(&mut graph, "b", &[a], &[b]);
+        add_pass(&mut graph, "output", &[a, b], &[output]);
+
+        assert_eq!(graph.compile(), Ok(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn readers_see_the_last_earlier_write() {
+        let (mut graph, output, a, _) = graph();
+
+        add_pass(&mut graph, "write a", &[], &[a]);
+        add_pass(&mut graph, "read a", &[a], &[output]);
+        add_pass(&mut graph, "overwrite a", &[], &[a]);
+        add_pass(&mut graph, "read a again", &[a], &[output]);
+
+        assert_eq!(graph.compile(), Ok(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn unused_passes_are_culled() {
+        let (mut graph, output, a, b) = graph();
+
+        add_pass(&mut graph, "unread", &[], &[b]);
+        add_pass(&mut graph, "write a", &[], &[a]);
+        add_pass(&mut graph, "output", &[a], &[output]);
+        add_pass(&mut graph, "read a too late", &[a], &[b]);
+        add_pass(&mut graph, "overwrite a", &[], &[a]);
+
+        assert_eq!(graph.compile(), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn outputs_keep_all_their_writers() {
+        let (mut graph, output, a, _) = graph();
+
+        add_pass(&mut graph, "clear", &[], &[output]);
+        add_pass(&mut graph, "write a", &[], &[a]);
+        add_pass(&mut graph, "draw", &[a], &[output]);
+
+        assert_eq!(graph.compile(), Ok(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn producers_added_later_run_first() {
+        let (mut graph, output, a, b) = graph();
+
+        add_pass(&mut graph, "lighting", &[a, b], &[output]);
+        add_pass(&mut graph, "g-buffer", &[], &[a, b]);
+
+        assert_eq!(graph.compile(), Ok(vec![1, 0]));
+    }
+
+    #[test]
+    fn writers_wait_for_readers_of_the_previous_version() {
+        let (mut graph, output, a, b) = graph();
+
+        add_pass(&mut graph, "write a", &[], &[a]);
+        add_pass(&mut graph, "read a and b", &[a, b], &[output]);
+        add_pass(&mut graph, "overwrite a", &[], &[a]);
+        add_pass(&mut graph, "write b", &[], &[b]);
+        add_pass(&mut graph, "read a again", &[a], &[output]);
+
+        assert_eq!(graph.compile(), Ok(vec![0, 3, 1, 2, 4]));
+    }
+
+    #[test]
+    fn cycles_fail() {
+        let (mut graph, output, a, b) = graph();
+
+        add_pass(&mut graph, "a", &[b], &[a]);
+        add_pass(&mut graph, "b", &[a], &[b, output]);
+
+        assert_eq!(
+            graph.compile(),
+            Err(RenderGraphError::Cycle(vec!["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn reading_unwritten_textures_fails() {
+        let (mut graph, output, a, _) = graph();
+
+        add_pass(&mut graph, "read a", &[a], &[output]);
+
+        assert_eq!(
+            graph.compile(),
+            Err(RenderGraphError::UnwrittenTexture("a"))
+        );
+    }
+
+    #[test]
+    fn culled_passes_may_read_unwritten_textures() {
+        let (mut graph, output, a, b) = graph();
+
+        add_pass(&mut graph, "culled", &[a], &[b]);
+        add_pass(&mut graph, "output", &[], &[output]);
+
+        assert_eq!(graph.compile(), Ok(vec![1]));
+    }
+}
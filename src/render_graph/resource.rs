@@ -0,0 +1,136 @@
+use std::{any::TypeId, marker::PhantomData};
+
+use cgmath::Vector2;
+use wgpu::{Device, TextureFormat};
+
+use crate::texture::{PixelFormat, RenderTexture};
+
+/// Identifies a texture or buffer inside of a [`RenderGraph`](super::RenderGraph).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResourceId(pub(super) usize);
+
+/// A transient texture of a [`RenderGraph`](super::RenderGraph).
+///
+/// The texture is only valid while the graph executes and can be accessed with [`PassResources::texture`](super::PassResources::texture).
+pub struct TextureHandle<P, const SAMPLES: u32 = 1> {
+    pub(super) id: ResourceId,
+    _phantom: PhantomData<P>,
+}
+
+impl<P, const SAMPLES: u32> TextureHandle<P, SAMPLES> {
+    pub(super) fn new(id: ResourceId) -> Self {
+        Self {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> ResourceId {
+        self.id
+    }
+}
+
+impl<P, const SAMPLES: u32> Clone for TextureHandle<P, SAMPLES> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P, const SAMPLES: u32> Copy for TextureHandle<P, SAMPLES> {}
+
+impl<P, const SAMPLES: u32> From<TextureHandle<P, SAMPLES>> for ResourceId {
+    fn from(value: TextureHandle<P, SAMPLES>) -> Self {
+        value.id
+    }
+}
+
+/// The size of a transient texture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextureSize {
+    /// Scaled relative to the output size of the graph.
+    Relative(f32),
+    Absolute(Vector2<u32>),
+}
+
+impl TextureSize {
+    pub fn resolve(self, output_size: Vector2<u32>) -> Vector2<u32> {
+        match self {
+            TextureSize::Relative(scale) => Vector2::new(
+                ((output_size.x as f32 * scale) as u32).max(1),
+                ((output_size.y as f32 * scale) as u32).max(1),
+            ),
+            TextureSize::Absolute(size) => size,
+        }
+    }
+}
+
+/// Two transient textures with the same key can share the same GPU texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct TextureKey {
+    pub type_id: TypeId,
+    pub size: Vector2<u32>,
+    pub format: TextureFormat,
+}
+
+pub(super) type CreateTexture = fn(&Device, Vector2<u32>, TextureFormat) -> Box<dyn std::any::Any>;
+
+pub(super) struct TransientTexture {
+    pub label: &'static str,
+    pub type_id: TypeId,
+    pub size: TextureSize,
+    pub format: TextureFormat,
+    pub create: CreateTexture,
+}
+
+impl TransientTexture {
+    pub fn new<P: PixelFormat + 'static, const SAMPLES: u32>(
+        label: &'static str,
+        size: TextureSize,
+    ) -> Self {
+        Self {
+            label,
+            type_id: TypeId::of::<RenderTexture<P, SAMPLES>>(),
+            size,
+            format: P::FORMAT,
+            create: |device, size, _| Box::new(RenderTexture::<P, SAMPLES>::new(device, size, None)),
+        }
+    }
+
+    pub fn new_format<const SAMPLES: u32>(
+        label: &'static str,
+        size: TextureSize,
+        format: TextureFormat,
+    ) -> Self {
+        Self {
+            label,
+            type_id: TypeId::of::<RenderTexture<(), SAMPLES>>(),
+            size,
+            format,
+            create: |device, size, format| {
+                Box::new(RenderTexture::<(), SAMPLES>::new_format(device, size, format, None))
+            },
+        }
+    }
+
+    pub fn key(&self, output_size: Vector2<u32>) -> TextureKey {
+        TextureKey {
+            type_id: self.type_id,
+            size: self.size.resolve(output_size),
+            format: self.format,
+        }
+    }
+}
+
+pub(super) enum Resource {
+    Transient(TransientTexture),
+    Imported { label: &'static str },
+}
+
+impl Resource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Resource::Transient(texture) => texture.label,
+            Resource::Imported { label } => label,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::any::Any;
+
+use super::resource::TextureKey;
+
+/// Keeps the transient textures of a [`RenderGraph`](super::RenderGraph) alive between frames.
+///
+/// Textures that are not used by a frame get dropped at the end of [`RenderGraph::execute`](super::RenderGraph::execute),
+/// so textures with an outdated size are freed after a resize.
+#[derive(Default)]
+pub struct TexturePool {
+    pub(super) textures: Vec<PooledTexture>,
+}
+
+pub(super) struct PooledTexture {
+    pub key: TextureKey,
+    pub texture: Box<dyn Any>,
+}
+
+impl TexturePool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of GPU textures currently owned by the pool.
+    pub fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+
+    pub(super) fn take(&mut self, key: &TextureKey) -> Option<PooledTexture> {
+        let idx = self.textures.iter().position(|texture| texture.key == *key)?;
+
+        Some(self.textures.swap_remove(idx))
+    }
+}
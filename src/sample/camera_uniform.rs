@@ -1,8 +1,8 @@
-use cgmath::{Matrix4, PerspectiveFov, Rad, Transform, Vector2, Vector3};
+use cgmath::{Angle, Matrix4, Rad, Rotation, Transform, Vector2, Vector3};
 use sifu_render_derive::Uniform;
 use wgpu::Sampler;
 
-use crate::{self as sifu_render, texture::{ColorPixel, ImageTexture}, uniform_binding::UniformBinding, UniformBuffer};
+use crate::{self as sifu_render, bounds::{DepthSorter, Frustum}, texture::{ColorPixel, DepthMode, ImageTexture}, uniform_binding::UniformBinding, UniformArrayBuffer, UniformSlice};
 
 use super::sample_vertex::SampleUniform;
 
@@ -11,6 +11,56 @@ pub struct Camera {
     pub rotation: cgmath::Basis3<f32>,
     pub fovy: Rad<f32>,
     pub screen_size: Vector2<f32>,
+    pub projection: Projection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    Standard { near: f32, far: f32 },
+    /// Maps `near` to depth 1 and `far` to depth 0, see [`DepthMode::ReverseZ`]
+    ReverseZ { near: f32, far: f32 },
+    /// Like [`Projection::ReverseZ`] with the far plane at infinity
+    ReverseZInfinite { near: f32 },
+}
+
+impl Default for Projection {
+    fn default() -> Self {
+        Self::Standard {
+            near: 0.1,
+            far: 1000.0,
+        }
+    }
+}
+
+impl Projection {
+    pub fn depth_mode(self) -> DepthMode {
+        match self {
+            Projection::Standard { .. } => DepthMode::Standard,
+            Projection::ReverseZ { .. } | Projection::ReverseZInfinite { .. } => DepthMode::ReverseZ,
+        }
+    }
+
+    /// Maps view space depth to wgpu's clip space depth range of `0..=1`.
+    pub fn matrix(self, fovy: Rad<f32>, aspect: f32) -> Matrix4<f32> {
+        // depth = (a * z + b) / -z
+        let (a, b) = match self {
+            Projection::Standard { near, far } => (far / (near - far), near * far / (near - far)),
+            Projection::ReverseZ { near, far } => (near / (far - near), near * far / (far - near)),
+            Projection::ReverseZInfinite { near } => (0.0, near),
+        };
+
+        let f = 1.0 / (fovy / 2.0).tan();
+
+        #[rustfmt::skip]
+        let matrix = Matrix4::new(
+            f / aspect, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, a, -1.0,
+            0.0, 0.0, b, 0.0,
+        );
+
+        matrix
+    }
 }
 
 impl Camera {
@@ -25,19 +75,17 @@ impl Camera {
 
         let aspect = self.screen_size.x / self.screen_size.y;
 
-        let proj = PerspectiveFov {
-            fovy: self.fovy,
-            aspect,
-            near: 0.1,
-            far: 1000.0,
-        };
-
-        let proj: Matrix4<_> = proj.into();
+        let proj = self.projection.matrix(self.fovy, aspect);
 
         CameraUniform {
             view_proj: proj * view,
         }
     }
+
+    /// Sorts transparent instances by their view space depth.
+    pub fn depth_sorter(&self) -> DepthSorter {
+        DepthSorter::new(self.position).with_view_direction(self.rotation.rotate_vector(-Vector3::unit_z()))
+    }
 }
 
 #[derive(Uniform)]
@@ -46,12 +94,67 @@ pub struct CameraUniform {
     pub view_proj: Matrix4<f32>,
 }
 
+impl CameraUniform {
+    pub fn frustum(&self) -> Frustum {
+        Frustum::from_view_proj(self.view_proj)
+    }
+}
+
 
 
 #[derive(UniformBinding)]
 pub struct FooUniformsDerive<'a> {
-    pub sample: &'a UniformBuffer<SampleUniform>,
-    pub camera: &'a UniformBuffer<CameraUniform>,
+    #[dynamic]
+    pub sample: &'a UniformArrayBuffer<SampleUniform>,
+    pub camera: &'a UniformSlice<CameraUniform>,
     pub texture: &'a ImageTexture<ColorPixel>,
     pub tex_sampler: &'a Sampler,
 }
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Deg, Vector4, assert_abs_diff_eq};
+
+    use super::Projection;
+
+    /// The normalized device depth of a point `distance` in front of the camera.
+    fn depth(projection: Projection, distance: f32) -> f32 {
+        let clip =
+            projection.matrix(Deg(60.0).into(), 1.5) * Vector4::new(0.0, 0.0, -distance, 1.0);
+
+        clip.z / clip.w
+    }
+
+    #[test]
+    fn standard_maps_near_to_0_and_far_to_1() {
+        let projection = Projection::Standard {
+            near: 0.1,
+            far: 100.0,
+        };
+
+        assert_abs_diff_eq!(depth(projection, 0.1), 0.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(depth(projection, 100.0), 1.0, epsilon = 1e-6);
+        assert!(depth(projection, 1.0) < depth(projection, 2.0));
+    }
+
+    #[test]
+    fn reverse_z_maps_near_to_1_and_far_to_0() {
+        let projection = Projection::ReverseZ {
+            near: 0.1,
+            far: 100.0,
+        };
+
+        assert_abs_diff_eq!(depth(projection, 0.1), 1.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(depth(projection, 100.0), 0.0, epsilon = 1e-6);
+        assert!(depth(projection, 1.0) > depth(projection, 2.0));
+    }
+
+    #[test]
+    fn infinite_reverse_z_approaches_0() {
+        let projection = Projection::ReverseZInfinite { near: 0.1 };
+
+        assert_abs_diff_eq!(depth(projection, 0.1), 1.0, epsilon = 1e-6);
+        assert!(depth(projection, 1e6) > 0.0);
+        assert!(depth(projection, 1e6) < 1e-6);
+    }
+}
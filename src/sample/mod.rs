@@ -7,6 +7,6 @@ use crate::shader::Shader;
 pub mod camera_uniform;
 pub mod sample_vertex;
 
-pub fn sample_shader(device: &Device) -> Shader<SampleVertex, SampleInstance, FooUniformsDerive> {
-    Shader::new(device, include_str!("sample_shader.wgsl"))
+pub fn sample_shader(device: &Device) -> Shader<SampleVertex, SampleInstance, FooUniformsDerive<'_>> {
+    Shader::new(device, include_str!("sample_shader.wgsl"), None)
 }
@@ -3,7 +3,7 @@ use wgpu::{
     BufferAddress, Device, VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode,
 };
 
-use crate::mesh::{Mesh, MeshInstance, Vertex};
+use crate::mesh::{Mesh, MeshInstance, Vertex, VertexAttributes};
 
 use crate::Uniform;
 use crate::{self as sifu_render};
@@ -59,9 +59,10 @@ impl From<SampleManualVertex> for SampleManualRawVertex {
     }
 }
 
-#[derive(Vertex)]
+#[derive(Vertex, VertexAttributes, Clone)]
 pub struct SampleVertex {
     #[raw(f32; 3)]
+    #[position]
     pub position: Vector3<f32>,
     #[raw(f32; 3)]
     pub color: Vector3<f32>,
@@ -71,6 +72,7 @@ pub struct SampleVertex {
 #[vertex(SampleVertex)]
 pub struct SampleInstance {
     #[raw(f32; 4; 4)]
+    #[position]
     pub mat: Matrix4<f32>,
 }
 
@@ -120,6 +122,6 @@ impl SampleVertex {
 
         let indices = vec![[0, 1, 2], [4, 3, 5]];
 
-        Mesh::new(device, vertices, indices)
+        Mesh::new_with_bounds(device, vertices, indices, None)
     }
 }
@@ -1,3 +1,5 @@
+mod pipeline;
 mod shader;
 
-pub use shader::Shader;
\ No newline at end of file
+pub use pipeline::Pipeline;
+pub use shader::Shader;
@@ -0,0 +1,30 @@
+use std::marker::PhantomData;
+
+use wgpu::{Device, RenderPipeline, RenderPipelineDescriptor};
+
+use crate::mesh::{Topology, TriangleList};
+
+/// A [`RenderPipeline`] that draws primitives of the topology `T`.
+pub struct Pipeline<T = TriangleList> {
+    pipeline: RenderPipeline,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Topology> Pipeline<T> {
+    /// `primitive.topology` and `primitive.strip_index_format` of `descriptor` are replaced by the values of `T`.
+    pub fn new(device: &Device, mut descriptor: RenderPipelineDescriptor) -> Self {
+        descriptor.primitive.topology = T::TOPOLOGY;
+        descriptor.primitive.strip_index_format = T::STRIP_INDEX_FORMAT;
+
+        Self {
+            pipeline: device.create_render_pipeline(&descriptor),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Pipeline<T> {
+    pub fn pipeline(&self) -> &RenderPipeline {
+        &self.pipeline
+    }
+}
@@ -0,0 +1,36 @@
+// Added to every shader by `Shader::new`
+
+// Skinning, see `sifu_render::animation`. Pass the matrices of the four joints of a vertex, e.g.
+// `skin_matrix(array(joints[vertex.joints.x].matrix, joints[vertex.joints.y].matrix, ...), vertex.weights)`
+fn skin_matrix(joint_matrices: array<mat4x4<f32>, 4>, weights: vec4<f32>) -> mat4x4<f32> {
+    return joint_matrices[0] * weights.x
+        + joint_matrices[1] * weights.y
+        + joint_matrices[2] * weights.z
+        + joint_matrices[3] * weights.w;
+}
+
+fn skin_position(skin: mat4x4<f32>, position: vec3<f32>) -> vec3<f32> {
+    return (skin * vec4<f32>(position, 1.0)).xyz;
+}
+
+// Assumes the joints are not scaled non-uniformly
+fn skin_normal(skin: mat4x4<f32>, normal: vec3<f32>) -> vec3<f32> {
+    return normalize((skin * vec4<f32>(normal, 0.0)).xyz);
+}
+
+// Morph targets, see `sifu_render::mesh::MorphTargets`. Bind the deltas as `array<MorphDelta>` and the weights as
+// `MorphWeights`, then add the weighted deltas of every target:
+// for (var t = 0u; t < weights.target_count; t++) {
+//     let delta = deltas[morph_index(t, weights.vertex_count, vertex_index, base_vertex)];
+//     position += delta.position * morph_weight(weights.weights, t);
+// }
+// `vertex_index` includes the base vertex of the draw, pass the first vertex of the morphed mesh in the vertex buffer
+// as `base_vertex`, e.g. `MeshHandle::base_vertex` of a `MeshPool` mesh. It is 0 for a `Mesh`, its submeshes share the
+// deltas of the whole mesh.
+fn morph_index(morph_target: u32, vertex_count: u32, vertex_index: u32, base_vertex: u32) -> u32 {
+    return morph_target * vertex_count + vertex_index - base_vertex;
+}
+
+fn morph_weight(weights: array<vec4<f32>, 4>, morph_target: u32) -> f32 {
+    return weights[morph_target / 4u][morph_target % 4u];
+}
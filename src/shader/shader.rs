@@ -1,30 +1,40 @@
-use std::marker::PhantomData;
+use std::{any::type_name, marker::PhantomData};
 
 use wgpu::{Device, PipelineCompilationOptions, ShaderModule, ShaderModuleDescriptor, VertexState};
 
 use crate::{
     mesh::{MeshInstance, Vertex},
+    texture::FragmentOutputs,
     uniform_binding::UniformBinding,
 };
 
-pub struct Shader<V, I, U0 = (), U1 = ()> {
+/// `O` describes the color targets of the fragment stage (see [`FragmentOutputs`]).
+/// Unless `O` is `()`, a `FragmentOutput` struct with one `@location(n) target{n}` field per target gets added to the shader.
+pub struct Shader<V, I, U0 = (), U1 = (), O = ()> {
     module: ShaderModule,
     code: String,
 
-    _phantom: PhantomData<(V, I, U0, U1)>,
+    _phantom: PhantomData<(V, I, U0, U1, O)>,
 }
 
-impl<V, I, U0, U1> Shader<V, I, U0, U1> {
+impl<V, I, U0, U1, O> Shader<V, I, U0, U1, O> {
     pub const ENTRY_POINT_VERTEX: &'static str = "vertex";
     pub const ENTRY_POINT_FRAGMENT: &'static str = "fragment";
+    pub const FRAGMENT_OUTPUT_STRUCT: &'static str = "FragmentOutput";
+
+    /// Helper functions added to the start of every shader, e.g. `skin_matrix` for skeletal animation.
+    pub const PRELUDE: &'static str = include_str!("prelude.wgsl");
 }
 
-impl<V: Vertex, I: MeshInstance, U0: UniformBinding, U1: UniformBinding> Shader<V, I, U0, U1> {
-    pub fn new(device: &Device, source: &str) -> Self {
+impl<V: Vertex, I: MeshInstance, U0: UniformBinding, U1: UniformBinding, O: FragmentOutputs>
+    Shader<V, I, U0, U1, O>
+{
+    /// The debug label defaults to the type name of the shader, which includes the vertex, instance and uniform types.
+    pub fn new(device: &Device, source: &str, label: Option<&str>) -> Self {
         let code = Self::preprocess_shader(source);
 
         let module = device.create_shader_module(ShaderModuleDescriptor {
-            label: Some("Shader"),
+            label: Some(label.unwrap_or(type_name::<Self>())),
             source: wgpu::ShaderSource::Wgsl(code.as_str().into()),
         });
 
@@ -44,9 +54,15 @@ impl<V: Vertex, I: MeshInstance, U0: UniformBinding, U1: UniformBinding> Shader<
         let instance_struct_code = I::shader_struct_str();
 
         let mut out = String::with_capacity(
-            source.len() + vertex_struct_code.len() + instance_struct_code.len(),
+            Self::PRELUDE.len()
+                + source.len()
+                + vertex_struct_code.len()
+                + instance_struct_code.len(),
         );
 
+        out += Self::PRELUDE;
+        out += "\n";
+
         out += vertex_struct_code;
         out += "\n";
 
@@ -56,6 +72,10 @@ impl<V: Vertex, I: MeshInstance, U0: UniformBinding, U1: UniformBinding> Shader<
         Self::append_uniforms::<U0>(0, &mut out);
         Self::append_uniforms::<U1>(1, &mut out);
 
+        if let Some(output_struct) = O::fragment_output_struct_str(Self::FRAGMENT_OUTPUT_STRUCT) {
+            out += &output_struct;
+        }
+
         out += source;
 
         out
@@ -78,12 +98,12 @@ impl<V: Vertex, I: MeshInstance, U0: UniformBinding, U1: UniformBinding> Shader<
     }
 }
 
-impl<V: Vertex, I: MeshInstance, U0, U1> Shader<V, I, U0, U1> {
+impl<V: Vertex, I: MeshInstance, U0, U1, O> Shader<V, I, U0, U1, O> {
     pub fn module(&self) -> &ShaderModule {
         &self.module
     }
 
-    pub fn vertex_state(&self) -> VertexState {
+    pub fn vertex_state(&self) -> VertexState<'_> {
         VertexState {
             module: &self.module,
             entry_point: Some(Self::ENTRY_POINT_VERTEX),
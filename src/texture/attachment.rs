@@ -0,0 +1,182 @@
+use wgpu::{DepthBiasState, DepthStencilState, LoadOp, Operations, StencilState, StoreOp};
+
+use super::{
+    ColorPixel, Depth16UnormPixel, Depth32FloatPixel, Depth32FloatStencil8Pixel, DepthMode,
+    DepthStencilPixel, DisplayPixel, PixelFormat, R32UintPixel, Rgba8Pixel, Rgba16FloatPixel,
+};
+
+/// Typed load and store operations of a single render pass attachment.
+///
+/// `C` is the clear value of the attachment, which depends on the [`PixelFormat`] of the attached texture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttachmentOps<C> {
+    pub load: LoadOp<C>,
+    pub store: StoreOp,
+}
+
+impl<C> AttachmentOps<C> {
+    /// Clears the attachment with `value` and stores the result.
+    pub const fn clear(value: C) -> Self {
+        Self {
+            load: LoadOp::Clear(value),
+            store: StoreOp::Store,
+        }
+    }
+
+    /// Keeps the previous content of the attachment and stores the result.
+    pub const fn load() -> Self {
+        Self {
+            load: LoadOp::Load,
+            store: StoreOp::Store,
+        }
+    }
+
+    /// Discards the content of the attachment at the end of the pass.
+    pub fn discard(self) -> Self {
+        Self {
+            load: self.load,
+            store: StoreOp::Discard,
+        }
+    }
+}
+
+impl<C: Copy> AttachmentOps<C> {
+    fn map<T>(&self, f: impl FnOnce(C) -> T) -> Operations<T> {
+        Operations {
+            load: match self.load {
+                LoadOp::Clear(value) => LoadOp::Clear(f(value)),
+                LoadOp::Load => LoadOp::Load,
+            },
+            store: self.store,
+        }
+    }
+}
+
+impl<C> From<AttachmentOps<C>> for Operations<C> {
+    fn from(value: AttachmentOps<C>) -> Self {
+        Operations {
+            load: value.load,
+            store: value.store,
+        }
+    }
+}
+
+/// Clear value of a combined depth and stencil attachment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthStencilClear {
+    pub depth: f32,
+    pub stencil: u32,
+}
+
+impl DepthStencilClear {
+    pub const fn new(depth: f32, stencil: u32) -> Self {
+        Self { depth, stencil }
+    }
+}
+
+/// Formats that can be used as a color attachment.
+///
+/// `()` is the format of a [`RenderTexture`](super::RenderTexture) with a runtime format, e.g. a surface texture.
+pub trait ColorAttachmentFormat {
+    const DEFAULT_CLEAR: wgpu::Color = wgpu::Color::BLACK;
+
+    /// The type a fragment shader writes to an attachment of this format.
+    const WGSL_OUTPUT_TYPE: &'static str = "vec4<f32>";
+}
+
+impl ColorAttachmentFormat for () {}
+impl ColorAttachmentFormat for ColorPixel {}
+impl ColorAttachmentFormat for DisplayPixel {}
+impl ColorAttachmentFormat for Rgba8Pixel {}
+impl ColorAttachmentFormat for Rgba16FloatPixel {}
+impl ColorAttachmentFormat for R32UintPixel {
+    const WGSL_OUTPUT_TYPE: &'static str = "u32";
+}
+
+/// Formats that can be used as a depth/stencil attachment.
+///
+/// The clear value is `f32` for depth only formats and [`DepthStencilClear`] for formats with a stencil aspect.
+pub trait DepthAttachmentFormat: PixelFormat {
+    type ClearValue: Copy;
+
+    /// The clear value for `depth`, the stencil is cleared with 0.
+    fn clear_value(depth: f32) -> Self::ClearValue;
+
+    fn depth_ops(ops: &AttachmentOps<Self::ClearValue>) -> Option<Operations<f32>>;
+    fn stencil_ops(ops: &AttachmentOps<Self::ClearValue>) -> Option<Operations<u32>>;
+
+    /// A depth state that writes depth and compares with the function of `mode`. Stencil testing is disabled.
+    fn depth_stencil_state(mode: DepthMode) -> DepthStencilState {
+        DepthStencilState {
+            format: Self::FORMAT,
+            depth_write_enabled: true,
+            depth_compare: mode.compare_function(),
+            stencil: StencilState::default(),
+            bias: DepthBiasState::default(),
+        }
+    }
+}
+
+impl DepthAttachmentFormat for DepthStencilPixel {
+    type ClearValue = DepthStencilClear;
+
+    fn clear_value(depth: f32) -> Self::ClearValue {
+        DepthStencilClear::new(depth, 0)
+    }
+
+    fn depth_ops(ops: &AttachmentOps<Self::ClearValue>) -> Option<Operations<f32>> {
+        Some(ops.map(|clear| clear.depth))
+    }
+
+    fn stencil_ops(ops: &AttachmentOps<Self::ClearValue>) -> Option<Operations<u32>> {
+        Some(ops.map(|clear| clear.stencil))
+    }
+}
+
+impl DepthAttachmentFormat for Depth32FloatStencil8Pixel {
+    type ClearValue = DepthStencilClear;
+
+    fn clear_value(depth: f32) -> Self::ClearValue {
+        DepthStencilClear::new(depth, 0)
+    }
+
+    fn depth_ops(ops: &AttachmentOps<Self::ClearValue>) -> Option<Operations<f32>> {
+        Some(ops.map(|clear| clear.depth))
+    }
+
+    fn stencil_ops(ops: &AttachmentOps<Self::ClearValue>) -> Option<Operations<u32>> {
+        Some(ops.map(|clear| clear.stencil))
+    }
+}
+
+impl DepthAttachmentFormat for Depth32FloatPixel {
+    type ClearValue = f32;
+
+    fn clear_value(depth: f32) -> Self::ClearValue {
+        depth
+    }
+
+    fn depth_ops(ops: &AttachmentOps<Self::ClearValue>) -> Option<Operations<f32>> {
+        Some((*ops).into())
+    }
+
+    fn stencil_ops(_: &AttachmentOps<Self::ClearValue>) -> Option<Operations<u32>> {
+        None
+    }
+}
+
+impl DepthAttachmentFormat for Depth16UnormPixel {
+    type ClearValue = f32;
+
+    fn clear_value(depth: f32) -> Self::ClearValue {
+        depth
+    }
+
+    fn depth_ops(ops: &AttachmentOps<Self::ClearValue>) -> Option<Operations<f32>> {
+        Some((*ops).into())
+    }
+
+    fn stencil_ops(_: &AttachmentOps<Self::ClearValue>) -> Option<Operations<u32>> {
+        None
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt::Write as _;
+
+use wgpu::{BlendState, ColorTargetState, ColorWrites, TextureFormat, TextureSampleType, TextureView};
+
+use super::{ColorAttachmentFormat, RenderTexture};
+
+/// The fragment shader outputs of a set of color targets.
+///
+/// `()` has no outputs, the fragment entry point then declares its return value itself.
+pub trait FragmentOutputs {
+    const COUNT: usize;
+
+    fn wgsl_output_types() -> Vec<&'static str>;
+
+    /// A struct with one `@location(n) target{n}` field per color target.
+    fn fragment_output_struct_str(name: &str) -> Option<String> {
+        if Self::COUNT == 0 {
+            return None;
+        }
+
+        let mut out = String::new();
+
+        writeln!(&mut out, "struct {} {{", name).ok()?;
+
+        for (location, ty) in Self::wgsl_output_types().into_iter().enumerate() {
+            writeln!(&mut out, "  @location({location}) target{location}: {ty},").ok()?;
+        }
+
+        writeln!(&mut out, "}}").ok()?;
+
+        Some(out)
+    }
+}
+
+impl FragmentOutputs for () {
+    const COUNT: usize = 0;
+
+    fn wgsl_output_types() -> Vec<&'static str> {
+        Vec::new()
+    }
+}
+
+/// One or more [`RenderTexture`]s that are rendered to in the same pass.
+///
+/// Implemented for a single [`RenderTexture`] and for tuples of up to 8 [`RenderTexture`]s that share the same `SAMPLES` value.
+pub trait ColorTargets<const SAMPLES: u32>: FragmentOutputs {
+    fn views(&self) -> Vec<&TextureView>;
+
+    fn formats(&self) -> Vec<TextureFormat>;
+
+    /// The [`ColorAttachmentFormat::DEFAULT_CLEAR`] of every target.
+    fn default_clears() -> Vec<wgpu::Color>;
+
+    /// The [`ColorTargetState`]s of a pipeline rendering to these targets.
+    ///
+    /// `blend` is only applied to targets with a float format, integer targets can't be blended.
+    fn color_target_states(&self, blend: Option<BlendState>) -> Vec<Option<ColorTargetState>> {
+        self.formats()
+            .into_iter()
+            .map(|format| {
+                let blendable = matches!(
+                    format.sample_type(None, None),
+                    Some(TextureSampleType::Float { .. })
+                );
+
+                Some(ColorTargetState {
+                    format,
+                    blend: blend.filter(|_| blendable),
+                    write_mask: ColorWrites::ALL,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<P: ColorAttachmentFormat, const SAMPLES: u32> FragmentOutputs
+    for RenderTexture<P, SAMPLES>
+{
+    const COUNT: usize = 1;
+
+    fn wgsl_output_types() -> Vec<&'static str> {
+        vec![P::WGSL_OUTPUT_TYPE]
+    }
+}
+
+impl<P: ColorAttachmentFormat, const SAMPLES: u32> ColorTargets<SAMPLES>
+    for RenderTexture<P, SAMPLES>
+{
+    fn views(&self) -> Vec<&TextureView> {
+        vec![self.view()]
+    }
+
+    fn formats(&self) -> Vec<TextureFormat> {
+        vec![self.format()]
+    }
+
+    fn default_clears() -> Vec<wgpu::Color> {
+        vec![P::DEFAULT_CLEAR]
+    }
+}
+
+macro_rules! impl_color_targets_tuple {
+    ($($p:ident: $idx:tt),*) => {
+        impl<$($p: ColorAttachmentFormat,)* const SAMPLES: u32> FragmentOutputs
+            for ($(RenderTexture<$p, SAMPLES>,)*)
+        {
+            const COUNT: usize = [$($idx),*].len();
+
+            fn wgsl_output_types() -> Vec<&'static str> {
+                vec![$($p::WGSL_OUTPUT_TYPE),*]
+            }
+        }
+
+        impl<$($p: ColorAttachmentFormat,)* const SAMPLES: u32> ColorTargets<SAMPLES>
+            for ($(RenderTexture<$p, SAMPLES>,)*)
+        {
+            fn views(&self) -> Vec<&TextureView> {
+                vec![$(self.$idx.view()),*]
+            }
+
+            fn formats(&self) -> Vec<TextureFormat> {
+                vec![$(self.$idx.format()),*]
+            }
+
+            fn default_clears() -> Vec<wgpu::Color> {
+                vec![$($p::DEFAULT_CLEAR),*]
+            }
+        }
+    };
+}
+
+impl_color_targets_tuple!(A: 0, B: 1);
+impl_color_targets_tuple!(A: 0, B: 1, C: 2);
+impl_color_targets_tuple!(A: 0, B: 1, C: 2, D: 3);
+impl_color_targets_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_color_targets_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_color_targets_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_color_targets_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
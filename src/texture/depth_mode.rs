@@ -0,0 +1,32 @@
+use wgpu::CompareFunction;
+
+/// How depth values are distributed in the depth buffer.
+///
+/// Has to match the projection matrix, see [`Projection`](crate::sample::camera_uniform::Projection).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepthMode {
+    /// Near is 0, far is 1
+    #[default]
+    Standard,
+    /// Near is 1, far is 0.
+    ///
+    /// Spreads the float precision more evenly over the depth range, which avoids z-fighting in large scenes.
+    ReverseZ,
+}
+
+impl DepthMode {
+    pub fn compare_function(self) -> CompareFunction {
+        match self {
+            DepthMode::Standard => CompareFunction::LessEqual,
+            DepthMode::ReverseZ => CompareFunction::GreaterEqual,
+        }
+    }
+
+    /// The depth value of the far plane
+    pub fn clear_depth(self) -> f32 {
+        match self {
+            DepthMode::Standard => 1.0,
+            DepthMode::ReverseZ => 0.0,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{any::type_name, marker::PhantomData};
 
 use bytemuck::cast_slice;
 use cgmath::Vector2;
@@ -6,6 +6,8 @@ use wgpu::{
     util::DeviceExt, Device, Extent3d, Queue, TexelCopyBufferLayout, Texture, TextureDescriptor, TextureUsages, TextureView, TextureViewDescriptor
 };
 
+use crate::memory_stats::MemoryAllocation;
+
 use super::{ColorPixel, PixelFormat, TextureRef};
 use std::path::Path;
 
@@ -13,12 +15,20 @@ pub struct ImageTexture<P = ColorPixel> {
     size: Vector2<u32>,
     texture: Texture,
     view: TextureView,
+    _memory: MemoryAllocation,
 
     _phantom: PhantomData<P>,
 }
 
 impl<P: PixelFormat> ImageTexture<P> {
-    pub fn new(device: &Device, queue: &Queue, size: Vector2<u32>, data: &[P::Pixel]) -> Self {
+    /// The debug label defaults to the type name of `P`.
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        size: Vector2<u32>,
+        data: &[P::Pixel],
+        label: Option<&str>,
+    ) -> Self {
         assert!(data.len() == (size.x * size.y) as usize);
 
         let data = cast_slice(data);
@@ -26,7 +36,7 @@ impl<P: PixelFormat> ImageTexture<P> {
         let texture = device.create_texture_with_data(
             queue,
             &TextureDescriptor {
-                label: None,
+                label: Some(label.unwrap_or(type_name::<P>())),
                 size: Extent3d {
                     width: size.x,
                     height: size.y,
@@ -49,6 +59,7 @@ impl<P: PixelFormat> ImageTexture<P> {
             size,
             texture,
             view,
+            _memory: MemoryAllocation::texture::<P>(label, size, P::FORMAT, 1),
             _phantom: PhantomData,
         }
     }
@@ -58,12 +69,13 @@ impl<P: PixelFormat> ImageTexture<P> {
         queue: &Queue,
         size: Vector2<u32>,
         f: impl Fn(Vector2<u32>) -> P::Pixel,
+        label: Option<&str>,
     ) -> Self {
         let indices = (0..size.y).flat_map(|y| (0..size.x).map(move |x| Vector2::new(x, y)));
 
         let data = indices.map(f).collect::<Vec<_>>();
 
-        Self::new(device, queue, size, &data)
+        Self::new(device, queue, size, &data, label)
     }
 
     pub fn write(&self, queue: &Queue, data: &[P::Pixel]) {
@@ -90,30 +102,34 @@ impl<P: PixelFormat> ImageTexture<P> {
 
 #[cfg(feature = "image")]
 impl ImageTexture<ColorPixel> {
+    /// The path is used as the debug label.
     pub fn load_image(
         device: &Device,
         queue: &Queue,
         path: impl AsRef<Path>,
     ) -> image::ImageResult<Self> {
-        let img = image::open(path)?;
+        let img = image::open(&path)?;
+        let label = path.as_ref().to_string_lossy();
 
-        Self::from_dynamic_img(device, queue, img)
+        Self::from_dynamic_img(device, queue, img, Some(&label))
     }
 
     pub fn load_image_from_memory(
         device: &Device,
         queue: &Queue,
         data: &[u8],
+        label: Option<&str>,
     ) -> image::ImageResult<Self> {
         let img = image::load_from_memory(data)?;
 
-        Self::from_dynamic_img(device, queue, img)
+        Self::from_dynamic_img(device, queue, img, label)
     }
 
     fn from_dynamic_img(
         device: &Device,
         queue: &Queue,
         img: image::DynamicImage,
+        label: Option<&str>,
     ) -> image::ImageResult<Self> {
         use image::GenericImageView;
 
@@ -123,7 +139,13 @@ impl ImageTexture<ColorPixel> {
 
         let data = cast_slice(&data_raw);
 
-        Ok(Self::new(device, queue, Vector2::new(width, height), &data))
+        Ok(Self::new(
+            device,
+            queue,
+            Vector2::new(width, height),
+            data,
+            label,
+        ))
     }
 }
 
@@ -138,7 +160,7 @@ impl<P> ImageTexture<P> {
         self.size
     }
 
-    pub fn texture_ref(&self) -> TextureRef<P, false>
+    pub fn texture_ref(&self) -> TextureRef<'_, P, 1>
     where
         P: PixelFormat,
     {
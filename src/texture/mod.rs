@@ -1,11 +1,19 @@
+mod attachment;
 mod image_texture;
 mod pixel_format;
+mod render_target;
 mod render_texture;
 mod color;
+mod color_targets;
+mod depth_mode;
 mod texture_ref;
 
+pub use attachment::*;
 pub use color::Color;
+pub use color_targets::{ColorTargets, FragmentOutputs};
+pub use depth_mode::DepthMode;
 pub use image_texture::ImageTexture;
+pub use render_target::RenderTarget;
 pub use render_texture::RenderTexture;
 pub use pixel_format::*;
 pub use texture_ref::TextureRef;
\ No newline at end of file
@@ -38,3 +38,59 @@ impl PixelFormat for DisplayPixel {
     const TEXTURE_SAMPLE_TYPE: TextureSampleType = TextureSampleType::Float { filterable: true };
     const GLSL_TEXTURE_TYPE: &'static str = "texture_2d<f32>";
 }
+
+pub struct Rgba8Pixel;
+
+impl PixelFormat for Rgba8Pixel {
+    type Pixel = Color;
+    const FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+    const TEXTURE_SAMPLE_TYPE: TextureSampleType = TextureSampleType::Float { filterable: true };
+    const GLSL_TEXTURE_TYPE: &'static str = "texture_2d<f32>";
+}
+
+pub struct Rgba16FloatPixel;
+
+impl PixelFormat for Rgba16FloatPixel {
+    /// Raw IEEE 754 half floats
+    type Pixel = [u16; 4];
+    const FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+    const TEXTURE_SAMPLE_TYPE: TextureSampleType = TextureSampleType::Float { filterable: true };
+    const GLSL_TEXTURE_TYPE: &'static str = "texture_2d<f32>";
+}
+
+pub struct R32UintPixel;
+
+impl PixelFormat for R32UintPixel {
+    type Pixel = u32;
+    const FORMAT: TextureFormat = TextureFormat::R32Uint;
+    const TEXTURE_SAMPLE_TYPE: TextureSampleType = TextureSampleType::Uint;
+    const GLSL_TEXTURE_TYPE: &'static str = "texture_2d<u32>";
+}
+
+pub struct Depth32FloatPixel;
+
+impl PixelFormat for Depth32FloatPixel {
+    type Pixel = f32;
+    const FORMAT: TextureFormat = TextureFormat::Depth32Float;
+    const TEXTURE_SAMPLE_TYPE: TextureSampleType = TextureSampleType::Depth;
+    const GLSL_TEXTURE_TYPE: &'static str = "texture_depth_2d";
+}
+
+pub struct Depth16UnormPixel;
+
+impl PixelFormat for Depth16UnormPixel {
+    type Pixel = u16;
+    const FORMAT: TextureFormat = TextureFormat::Depth16Unorm;
+    const TEXTURE_SAMPLE_TYPE: TextureSampleType = TextureSampleType::Depth;
+    const GLSL_TEXTURE_TYPE: &'static str = "texture_depth_2d";
+}
+
+/// Requires [`wgpu::Features::DEPTH32FLOAT_STENCIL8`]
+pub struct Depth32FloatStencil8Pixel;
+
+impl PixelFormat for Depth32FloatStencil8Pixel {
+    type Pixel = ();
+    const FORMAT: TextureFormat = TextureFormat::Depth32FloatStencil8;
+    const TEXTURE_SAMPLE_TYPE: TextureSampleType = TextureSampleType::Depth;
+    const GLSL_TEXTURE_TYPE: &'static str = "texture_depth_2d";
+}
@@ -0,0 +1,158 @@
+use wgpu::{
+    BlendState, ColorTargetState, CommandEncoder, MultisampleState, RenderPass,
+    RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor, TextureView,
+};
+
+use super::{
+    AttachmentOps, ColorAttachmentFormat, ColorTargets, DepthAttachmentFormat, DepthMode,
+    DepthStencilPixel, RenderTexture,
+};
+
+/// The color and optional depth attachments of a render pass.
+///
+/// `C` is either a single [`RenderTexture`] or a tuple of [`RenderTexture`]s (see [`ColorTargets`]).
+/// The color and the depth textures always share the same `SAMPLES` value, so mismatching attachments are rejected at compile time.
+pub struct RenderTarget<'a, C, D = DepthStencilPixel, const SAMPLES: u32 = 1>
+where
+    D: DepthAttachmentFormat,
+{
+    color: &'a C,
+    resolve: Option<&'a TextureView>,
+    depth: Option<&'a RenderTexture<D, SAMPLES>>,
+
+    /// One per color target
+    color_ops: Vec<AttachmentOps<wgpu::Color>>,
+    depth_ops: AttachmentOps<D::ClearValue>,
+}
+
+impl<'a, C, D, const SAMPLES: u32> RenderTarget<'a, C, D, SAMPLES>
+where
+    C: ColorTargets<SAMPLES>,
+    D: DepthAttachmentFormat,
+{
+    pub fn new(color: &'a C) -> Self {
+        Self {
+            color,
+            resolve: None,
+            depth: None,
+            color_ops: C::default_clears()
+                .into_iter()
+                .map(AttachmentOps::clear)
+                .collect(),
+            depth_ops: AttachmentOps::clear(D::clear_value(DepthMode::Standard.clear_depth())),
+        }
+    }
+}
+
+impl<'a, P, D, const SAMPLES: u32> RenderTarget<'a, RenderTexture<P, SAMPLES>, D, SAMPLES>
+where
+    P: ColorAttachmentFormat,
+    D: DepthAttachmentFormat,
+{
+    /// Renders into `multisample_color` and resolves the result into `resolve_color`.
+    pub fn resolve(
+        multisample_color: &'a RenderTexture<P, SAMPLES>,
+        resolve_color: &'a RenderTexture<P>,
+    ) -> Self {
+        const { assert!(SAMPLES > 1, "only multisampled textures can be resolved") };
+
+        Self {
+            resolve: Some(resolve_color.view()),
+            ..Self::new(multisample_color)
+        }
+    }
+}
+
+impl<'a, C, D, const SAMPLES: u32> RenderTarget<'a, C, D, SAMPLES>
+where
+    C: ColorTargets<SAMPLES>,
+    D: DepthAttachmentFormat,
+{
+    pub fn with_depth(self, depth: &'a RenderTexture<D, SAMPLES>) -> Self {
+        Self {
+            depth: Some(depth),
+            ..self
+        }
+    }
+
+    /// Sets the ops of all color attachments.
+    pub fn with_color_ops(self, ops: AttachmentOps<wgpu::Color>) -> Self {
+        Self {
+            color_ops: vec![ops; self.color_ops.len()],
+            ..self
+        }
+    }
+
+    /// Sets the ops of the color attachment at `index`, e.g. to clear an integer ID target differently than the others.
+    ///
+    /// # Panics
+    /// If there is no color target at `index`.
+    pub fn with_target_ops(mut self, index: usize, ops: AttachmentOps<wgpu::Color>) -> Self {
+        self.color_ops[index] = ops;
+        self
+    }
+
+    pub fn with_depth_ops(self, depth_ops: AttachmentOps<D::ClearValue>) -> Self {
+        Self { depth_ops, ..self }
+    }
+
+    /// Clears the depth attachment with the far plane depth of `mode`.
+    pub fn with_depth_mode(self, mode: DepthMode) -> Self {
+        self.with_depth_ops(AttachmentOps::clear(D::clear_value(mode.clear_depth())))
+    }
+
+    pub fn sample_count(&self) -> u32 {
+        RenderTexture::<(), SAMPLES>::sample_count()
+    }
+
+    pub fn multisample_state(&self) -> MultisampleState {
+        MultisampleState {
+            count: self.sample_count(),
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        }
+    }
+
+    pub fn color_target_states(&self, blend: Option<BlendState>) -> Vec<Option<ColorTargetState>> {
+        self.color.color_target_states(blend)
+    }
+
+    pub fn color_attachments(&self) -> Vec<Option<RenderPassColorAttachment<'_>>> {
+        self.color
+            .views()
+            .into_iter()
+            .zip(&self.color_ops)
+            .enumerate()
+            .map(|(idx, (view, ops))| {
+                Some(RenderPassColorAttachment {
+                    view,
+                    depth_slice: None,
+                    resolve_target: self.resolve.filter(|_| idx == 0),
+                    ops: (*ops).into(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn depth_stencil_attachment(&self) -> Option<RenderPassDepthStencilAttachment<'_>> {
+        self.depth.map(|depth| RenderPassDepthStencilAttachment {
+            view: depth.view(),
+            depth_ops: D::depth_ops(&self.depth_ops),
+            stencil_ops: D::stencil_ops(&self.depth_ops),
+        })
+    }
+
+    pub fn begin_pass<'e>(
+        &self,
+        encoder: &'e mut CommandEncoder,
+        label: Option<&str>,
+    ) -> RenderPass<'e> {
+        encoder.begin_render_pass(&RenderPassDescriptor {
+            label,
+            color_attachments: &self.color_attachments(),
+            depth_stencil_attachment: self.depth_stencil_attachment(),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        })
+    }
+}
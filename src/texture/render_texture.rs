@@ -1,45 +1,69 @@
-use std::marker::PhantomData;
+use std::{any::type_name, marker::PhantomData};
 
 use cgmath::Vector2;
 use wgpu::{
-    Device, Extent3d, SurfaceTexture, Texture, TextureDescriptor, TextureFormat, TextureUsages,
+    Adapter, Device, Extent3d, SurfaceTexture, Texture, TextureDescriptor, TextureFormat, TextureUsages,
     TextureView, TextureViewDescriptor,
 };
 
+use crate::memory_stats::MemoryAllocation;
+
 use super::{PixelFormat, TextureRef};
 
-pub struct RenderTexture<P, const MULTISAMPLE: bool = false> {
+/// `SAMPLES` is the MSAA sample count of the texture and has to be 1, 2, 4, 8 or 16.
+pub struct RenderTexture<P, const SAMPLES: u32 = 1> {
     size: Vector2<u32>,
     texture: TextureStore,
     view: TextureView,
 
     format: TextureFormat,
+    /// `None` if the texture uses the default label
+    label: Option<String>,
     _phantom: PhantomData<P>,
 }
 
 #[allow(unused)]
 enum TextureStore {
     OnlyTextureView,
-    Texture(Texture),
+    Texture(Texture, MemoryAllocation),
     SurfaceTexture(SurfaceTexture),
 }
 
-impl<P: PixelFormat, const MULTISAMPLE: bool> RenderTexture<P, MULTISAMPLE> {
-    pub fn new(device: &Device, size: Vector2<u32>) -> Self {
-        Self::new_any_format(device, size, P::FORMAT)
+impl<P: PixelFormat, const SAMPLES: u32> RenderTexture<P, SAMPLES> {
+    /// The debug label defaults to the type name of `P`.
+    pub fn new(device: &Device, size: Vector2<u32>, label: Option<&str>) -> Self {
+        Self::new_any_format(device, size, P::FORMAT, label)
+    }
+
+    /// Whether `adapter` supports `SAMPLES` for the format of this texture.
+    pub fn is_supported(adapter: &Adapter) -> bool {
+        Self::is_format_supported(adapter, P::FORMAT)
     }
 }
 
-impl<const MULTISAMPLE: bool> RenderTexture<(), MULTISAMPLE> {
-    pub fn new_format(device: &Device, size: Vector2<u32>, format: TextureFormat) -> Self {
-        Self::new_any_format(device, size, format)
+impl<const SAMPLES: u32> RenderTexture<(), SAMPLES> {
+    /// The debug label defaults to the name of `format`.
+    pub fn new_format(
+        device: &Device,
+        size: Vector2<u32>,
+        format: TextureFormat,
+        label: Option<&str>,
+    ) -> Self {
+        Self::new_any_format(device, size, format, label)
     }
 }
 
-impl<P, const MULTISAMPLE: bool> RenderTexture<P, MULTISAMPLE> {
-    fn new_any_format(device: &Device, size: Vector2<u32>, format: TextureFormat) -> Self {
+impl<P, const SAMPLES: u32> RenderTexture<P, SAMPLES> {
+    fn new_any_format(
+        device: &Device,
+        size: Vector2<u32>,
+        format: TextureFormat,
+        label: Option<&str>,
+    ) -> Self {
+        let default_label = Self::default_label(format);
+
         let texture = device.create_texture(&TextureDescriptor {
-            label: None,
+            label: Some(label.unwrap_or(&default_label)),
             size: Extent3d {
                 width: size.x,
                 height: size.y,
@@ -57,25 +81,30 @@ impl<P, const MULTISAMPLE: bool> RenderTexture<P, MULTISAMPLE> {
 
         Self {
             size,
-            texture: TextureStore::Texture(texture),
+            texture: TextureStore::Texture(
+                texture,
+                MemoryAllocation::texture::<P>(label, size, format, Self::sample_count()),
+            ),
             view,
             format,
+            label: label.map(String::from),
             _phantom: PhantomData,
         }
     }
 
     pub fn resize(&mut self, device: &Device, size: Vector2<u32>) {
         if self.size != size {
-            *self = Self::new_any_format(device, size, self.format);
+            *self = Self::new_any_format(device, size, self.format, self.label.as_deref());
         }
     }
 
-    pub fn to_any_format(self) -> RenderTexture<(), MULTISAMPLE> {
+    pub fn to_any_format(self) -> RenderTexture<(), SAMPLES> {
         RenderTexture {
             size: self.size,
             texture: self.texture,
             view: self.view,
             format: self.format,
+            label: self.label,
             _phantom: PhantomData,
         }
     }
@@ -84,18 +113,44 @@ impl<P, const MULTISAMPLE: bool> RenderTexture<P, MULTISAMPLE> {
         self.format
     }
 
-    fn sample_count() -> u32 {
-        if MULTISAMPLE { 4 } else { 1 }
+    /// The type name of `P`, or the name of `format` for textures without a [`PixelFormat`].
+    fn default_label(format: TextureFormat) -> String {
+        match type_name::<P>() {
+            "()" => format!("{:?}", format),
+            name => name.to_string(),
+        }
+    }
+
+    pub fn sample_count() -> u32 {
+        const {
+            assert!(
+                matches!(SAMPLES, 1 | 2 | 4 | 8 | 16),
+                "the sample count of a texture has to be 1, 2, 4, 8 or 16"
+            )
+        };
+
+        SAMPLES
+    }
+
+    /// Whether `adapter` supports `SAMPLES` for `format`.
+    ///
+    /// Sample counts other than 1 and 4 additionally require [`wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`] on the device.
+    pub fn is_format_supported(adapter: &Adapter, format: TextureFormat) -> bool {
+        adapter
+            .get_texture_format_features(format)
+            .flags
+            .sample_count_supported(SAMPLES)
     }
 }
 
-impl RenderTexture<(), false> {
+impl RenderTexture<(), 1> {
     pub fn from_view(view: TextureView, size: Vector2<u32>, format: TextureFormat) -> Self {
         Self {
             size,
             texture: TextureStore::OnlyTextureView,
             view,
             format,
+            label: None,
             _phantom: PhantomData,
         }
     }
@@ -117,19 +172,19 @@ impl RenderTexture<(), false> {
             texture: TextureStore::SurfaceTexture(surface_texture),
             view,
             format,
+            label: None,
             _phantom: PhantomData,
         }
     }
 
     pub fn present(self) {
-        match self.texture {
-            TextureStore::SurfaceTexture(surface_texture) => surface_texture.present(),
-            _ => (),
+        if let TextureStore::SurfaceTexture(surface_texture) = self.texture {
+            surface_texture.present();
         }
     }
 }
 
-impl<P, const MULTISAMPLE: bool> RenderTexture<P, MULTISAMPLE> {
+impl<P, const SAMPLES: u32> RenderTexture<P, SAMPLES> {
     const TEXTURE_USAGES: TextureUsages =
         TextureUsages::TEXTURE_BINDING.union(TextureUsages::RENDER_ATTACHMENT);
 
@@ -141,7 +196,7 @@ impl<P, const MULTISAMPLE: bool> RenderTexture<P, MULTISAMPLE> {
         self.size
     }
 
-    pub fn texture_ref(&self) -> TextureRef<P, MULTISAMPLE> {
+    pub fn texture_ref(&self) -> TextureRef<'_, P, SAMPLES> {
         TextureRef {
             view: self.view(),
             format: self.format(),
@@ -4,7 +4,7 @@ use wgpu::{TextureFormat, TextureView};
 
 use super::ColorPixel;
 
-pub struct TextureRef<'a, P = ColorPixel, const MULTISAMPLE: bool = false> {
+pub struct TextureRef<'a, P = ColorPixel, const SAMPLES: u32 = 1> {
     pub view: &'a TextureView,
     pub format: TextureFormat,
     pub _phantom: PhantomData<P>,
@@ -1,19 +1,35 @@
-use wgpu::{BindingResource, BindingType, Sampler};
+use wgpu::{BindingResource, BindingType, DynamicOffset, Sampler};
 
-use crate::{texture::{ImageTexture, PixelFormat, RenderTexture, TextureRef}, uniform_binding::GlslUniformType, Uniform, UniformBuffer};
+use crate::{texture::{ImageTexture, PixelFormat, RenderTexture, TextureRef}, uniform_binding::GlslUniformType, StorageBuffer, Uniform, UniformArrayBuffer, UniformBuffer, UniformSlice};
 
 
 pub trait AsBindingResource {
     const LAYOUT: BindingType;
-    fn bind_resource(&self) -> BindingResource;
+
+    /// Fields of this type need the `#[dynamic]` attribute in a [`UniformBinding`](super::UniformBinding).
+    const HAS_DYNAMIC_OFFSET: bool = matches!(
+        Self::LAYOUT,
+        BindingType::Buffer {
+            has_dynamic_offset: true,
+            ..
+        }
+    );
+
+    fn bind_resource(&self) -> BindingResource<'_>;
     fn glsl_type() -> GlslUniformType;
 }
 
+/// A resource whose binding has a dynamic offset, see the `#[dynamic]` attribute of [`UniformBinding`](super::UniformBinding).
+pub trait DynamicBindingResource: AsBindingResource {
+    /// The dynamic offset selecting the element at `index`.
+    fn dynamic_offset(&self, index: u32) -> DynamicOffset;
+}
+
 impl<T: AsBindingResource> AsBindingResource for &T {
     const LAYOUT: BindingType = T::LAYOUT;
 
-    fn bind_resource(&self) -> BindingResource {
-        <T as AsBindingResource>::bind_resource(&self)
+    fn bind_resource(&self) -> BindingResource<'_> {
+        <T as AsBindingResource>::bind_resource(self)
     }
 
     fn glsl_type() -> GlslUniformType {
@@ -28,19 +44,81 @@ impl<T: Uniform> AsBindingResource for UniformBuffer<T> {
         min_binding_size: None,
     };
 
-    fn bind_resource(&self) -> BindingResource {
+    fn bind_resource(&self) -> BindingResource<'_> {
         BindingResource::Buffer(self.binding())
     }
 
     fn glsl_type() -> GlslUniformType {
         GlslUniformType {
             is_uniform: true,
+            is_storage_array: false,
             type_name: T::shader_struct_name(),
             type_struct_str: Some(T::shader_struct_str()),
         }
     }
 }
 
+impl<T: DynamicBindingResource> DynamicBindingResource for &T {
+    fn dynamic_offset(&self, index: u32) -> DynamicOffset {
+        T::dynamic_offset(self, index)
+    }
+}
+
+impl<T: Uniform> AsBindingResource for UniformArrayBuffer<T> {
+    const LAYOUT: BindingType = BindingType::Buffer {
+        ty: wgpu::BufferBindingType::Uniform,
+        has_dynamic_offset: true,
+        min_binding_size: None,
+    };
+
+    fn bind_resource(&self) -> BindingResource<'_> {
+        BindingResource::Buffer(self.element_binding())
+    }
+
+    fn glsl_type() -> GlslUniformType {
+        UniformBuffer::<T>::glsl_type()
+    }
+}
+
+impl<T: Uniform> DynamicBindingResource for UniformArrayBuffer<T> {
+    fn dynamic_offset(&self, index: u32) -> DynamicOffset {
+        self.offset(index)
+    }
+}
+
+impl<T: Uniform> AsBindingResource for StorageBuffer<T> {
+    const LAYOUT: BindingType = BindingType::Buffer {
+        ty: wgpu::BufferBindingType::Storage { read_only: true },
+        has_dynamic_offset: false,
+        min_binding_size: None,
+    };
+
+    fn bind_resource(&self) -> BindingResource<'_> {
+        BindingResource::Buffer(self.binding())
+    }
+
+    fn glsl_type() -> GlslUniformType {
+        GlslUniformType {
+            is_uniform: false,
+            is_storage_array: true,
+            type_name: T::shader_struct_name(),
+            type_struct_str: Some(T::shader_struct_str()),
+        }
+    }
+}
+
+impl<T: Uniform> AsBindingResource for UniformSlice<T> {
+    const LAYOUT: BindingType = UniformBuffer::<T>::LAYOUT;
+
+    fn bind_resource(&self) -> BindingResource<'_> {
+        BindingResource::Buffer(self.binding())
+    }
+
+    fn glsl_type() -> GlslUniformType {
+        UniformBuffer::<T>::glsl_type()
+    }
+}
+
 impl<P: PixelFormat> AsBindingResource for ImageTexture<P> {
     const LAYOUT: BindingType = BindingType::Texture {
         sample_type: P::TEXTURE_SAMPLE_TYPE,
@@ -48,56 +126,59 @@ impl<P: PixelFormat> AsBindingResource for ImageTexture<P> {
         multisampled: false,
     };
 
-    fn bind_resource(&self) -> BindingResource {
+    fn bind_resource(&self) -> BindingResource<'_> {
         BindingResource::TextureView(self.view())
     }
 
     fn glsl_type() -> GlslUniformType {
         GlslUniformType {
             is_uniform: false,
+            is_storage_array: false,
             type_name: P::GLSL_TEXTURE_TYPE,
             type_struct_str: None,
         }
     }
 }
 
-impl<P: PixelFormat, const MULTISAMPLE: bool> AsBindingResource for RenderTexture<P, MULTISAMPLE> {
+impl<P: PixelFormat, const SAMPLES: u32> AsBindingResource for RenderTexture<P, SAMPLES> {
     const LAYOUT: BindingType = BindingType::Texture {
         sample_type: P::TEXTURE_SAMPLE_TYPE,
         view_dimension: wgpu::TextureViewDimension::D2,
-        multisampled: MULTISAMPLE,
+        multisampled: SAMPLES > 1,
     };
 
-    fn bind_resource(&self) -> BindingResource {
+    fn bind_resource(&self) -> BindingResource<'_> {
         BindingResource::TextureView(self.view())
     }
 
     fn glsl_type() -> GlslUniformType {
         GlslUniformType {
             is_uniform: false,
+            is_storage_array: false,
             type_name: P::GLSL_TEXTURE_TYPE,
             type_struct_str: None,
         }
     }
 }
 
-impl<'a, P, const MULTISAMPLE: bool> AsBindingResource for TextureRef<'a, P, MULTISAMPLE>
+impl<'a, P, const SAMPLES: u32> AsBindingResource for TextureRef<'a, P, SAMPLES>
 where
     P: PixelFormat,
 {
     const LAYOUT: BindingType = BindingType::Texture {
         sample_type: P::TEXTURE_SAMPLE_TYPE,
         view_dimension: wgpu::TextureViewDimension::D2,
-        multisampled: MULTISAMPLE,
+        multisampled: SAMPLES > 1,
     };
 
-    fn bind_resource(&self) -> BindingResource {
+    fn bind_resource(&self) -> BindingResource<'_> {
         BindingResource::TextureView(self.view)
     }
 
     fn glsl_type() -> GlslUniformType {
         GlslUniformType {
             is_uniform: false,
+            is_storage_array: false,
             type_name: P::GLSL_TEXTURE_TYPE,
             type_struct_str: None,
         }
@@ -107,13 +188,14 @@ where
 impl AsBindingResource for Sampler {
     const LAYOUT: BindingType = BindingType::Sampler(wgpu::SamplerBindingType::Filtering);
 
-    fn bind_resource(&self) -> BindingResource {
+    fn bind_resource(&self) -> BindingResource<'_> {
         BindingResource::Sampler(self)
     }
 
     fn glsl_type() -> GlslUniformType {
         GlslUniformType {
             is_uniform: false,
+            is_storage_array: false,
             type_name: "sampler",
             type_struct_str: None,
         }
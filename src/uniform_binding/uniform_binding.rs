@@ -3,7 +3,7 @@ use std::{any::type_name, fmt::Display, sync::OnceLock};
 pub use sifu_render_derive::UniformBinding;
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
-    BindGroupLayoutEntry, Device, Sampler, ShaderStages,
+    BindGroupLayoutEntry, Device, DynamicOffset, Sampler, ShaderStages,
 };
 
 use crate::{
@@ -16,7 +16,23 @@ use crate::{
 pub trait UniformBinding {
     const LAYOUT: &'static [BindGroupLayoutEntry];
 
-    fn binding_entries(&self) -> Vec<BindGroupEntry>;
+    /// The number of fields marked with `#[dynamic]`.
+    const DYNAMIC_OFFSET_COUNT: usize = 0;
+
+    fn binding_entries(&self) -> Vec<BindGroupEntry<'_>>;
+
+    /// The dynamic offsets for [`RenderPass::set_bind_group`](wgpu::RenderPass::set_bind_group) in binding order.
+    ///
+    /// `indices` contains the element index of every `#[dynamic]` field, in field order.
+    fn dynamic_offsets(&self, indices: &[u32]) -> Vec<DynamicOffset> {
+        assert_eq!(
+            indices.len(),
+            Self::DYNAMIC_OFFSET_COUNT,
+            "expected one index per dynamic binding"
+        );
+
+        Vec::new()
+    }
 
     fn glsl_vars(group_id: u32) -> Vec<GlslUniformVar>;
 
@@ -26,7 +42,7 @@ pub trait UniformBinding {
 impl UniformBinding for () {
     const LAYOUT: &'static [BindGroupLayoutEntry] = &[];
 
-    fn binding_entries(&self) -> Vec<BindGroupEntry> {
+    fn binding_entries(&self) -> Vec<BindGroupEntry<'_>> {
         Vec::new()
     }
 
@@ -95,7 +111,7 @@ impl<'a> UniformBinding for FooUniforms<'a> {
         },
     ];
 
-    fn binding_entries(&self) -> Vec<BindGroupEntry> {
+    fn binding_entries(&self) -> Vec<BindGroupEntry<'_>> {
         vec![
             BindGroupEntry {
                 binding: 0,
@@ -146,12 +162,22 @@ pub struct GlslUniformVar {
 
 pub struct GlslUniformType {
     pub is_uniform: bool,
+    /// A read-only storage buffer holding an `array` of the type
+    pub is_storage_array: bool,
     pub type_name: &'static str,
     pub type_struct_str: Option<&'static str>,
 }
 
 impl Display for GlslUniformVar {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.uniform.is_storage_array {
+            return write!(
+                f,
+                "@group({}) @binding({}) var<storage, read> {}: array<{}>",
+                self.group_id, self.binding_id, self.name, self.uniform.type_name
+            );
+        }
+
         write!(
             f,
             "@group({}) @binding({}) var{} {}: {}",